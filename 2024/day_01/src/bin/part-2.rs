@@ -0,0 +1,13 @@
+use day_01::{explain, process_part_two};
+use std::{env, fs};
+
+fn main() {
+    let input = fs::read_to_string("input.txt").unwrap();
+    println!("{}", process_part_two(&input));
+    if env::args().any(|arg| arg == "--explain") {
+        println!(
+            "{}",
+            explain::<u32>(&input).unwrap_or_else(|e| panic!("{}", e))
+        )
+    }
+}
@@ -0,0 +1,14 @@
+use day_01::report;
+use std::{env, fs};
+
+const TOP_CONTRIBUTORS: usize = 10;
+
+fn main() {
+    let input = fs::read_to_string("input.txt").unwrap();
+    let report = report::<u32>(&input, TOP_CONTRIBUTORS).unwrap_or_else(|e| panic!("{}", e));
+    if env::args().any(|arg| arg == "--csv") {
+        print!("{}", report.to_csv())
+    } else {
+        print!("{}", report)
+    }
+}
@@ -0,0 +1,986 @@
+use nom::{
+    character::complete::{char, digit1, space1},
+    combinator::{all_consuming, map_res, opt, recognize},
+    multi::separated_list1,
+    sequence::pair,
+    IResult,
+};
+use std::collections::hash_map::HashMap;
+use std::fmt;
+use std::hash::Hash;
+use std::iter::zip;
+use std::str::FromStr;
+
+/// Integer types location IDs can be parsed as.
+pub trait LocationId: Copy + Ord + Hash + FromStr + fmt::Debug + fmt::Display {
+    /// Absolute difference, which always fits in a `u64`.
+    fn abs_diff(self, other: Self) -> u64;
+    /// Absolute value.
+    fn magnitude(self) -> u64;
+}
+
+impl LocationId for u32 {
+    fn abs_diff(self, other: Self) -> u64 {
+        u32::abs_diff(self, other) as u64
+    }
+
+    fn magnitude(self) -> u64 {
+        self as u64
+    }
+}
+
+impl LocationId for u64 {
+    fn abs_diff(self, other: Self) -> u64 {
+        u64::abs_diff(self, other)
+    }
+
+    fn magnitude(self) -> u64 {
+        self
+    }
+}
+
+impl LocationId for i64 {
+    fn abs_diff(self, other: Self) -> u64 {
+        i64::abs_diff(self, other)
+    }
+
+    fn magnitude(self) -> u64 {
+        self.unsigned_abs()
+    }
+}
+
+/// A distance that can be summed over every pair without silently overflowing.
+pub trait Total: Copy + Default {
+    fn checked_add(self, other: Self) -> Option<Self>;
+}
+
+impl Total for u64 {
+    fn checked_add(self, other: Self) -> Option<Self> {
+        u64::checked_add(self, other)
+    }
+}
+
+impl Total for f64 {
+    fn checked_add(self, other: Self) -> Option<Self> {
+        Some(self + other).filter(|total| total.is_finite())
+    }
+}
+
+/// How far apart a pair of location IDs are.
+pub trait DistanceMetric<T: LocationId> {
+    type Output: Total;
+
+    /// Returns `None` if the distance doesn't fit in `Output`.
+    fn distance(&self, a: T, b: T) -> Option<Self::Output>;
+}
+
+/// `|a - b|`, as in the puzzle.
+pub struct Absolute;
+
+/// `(a - b)^2`
+pub struct Squared;
+
+/// `|a - b|` as a fraction of the larger of `|a|` and `|b|`.
+pub struct Relative;
+
+/// `|a - b|`, but never more than the cap.
+pub struct Capped(pub u64);
+
+impl<T: LocationId> DistanceMetric<T> for Absolute {
+    type Output = u64;
+
+    fn distance(&self, a: T, b: T) -> Option<u64> {
+        Some(a.abs_diff(b))
+    }
+}
+
+impl<T: LocationId> DistanceMetric<T> for Squared {
+    type Output = u64;
+
+    fn distance(&self, a: T, b: T) -> Option<u64> {
+        let diff = a.abs_diff(b);
+        diff.checked_mul(diff)
+    }
+}
+
+impl<T: LocationId> DistanceMetric<T> for Relative {
+    type Output = f64;
+
+    fn distance(&self, a: T, b: T) -> Option<f64> {
+        let largest = a.magnitude().max(b.magnitude());
+        if largest == 0 {
+            Some(0.0)
+        } else {
+            Some(a.abs_diff(b) as f64 / largest as f64)
+        }
+    }
+}
+
+impl<T: LocationId> DistanceMetric<T> for Capped {
+    type Output = u64;
+
+    fn distance(&self, a: T, b: T) -> Option<u64> {
+        Some(a.abs_diff(b).min(self.0))
+    }
+}
+
+#[derive(Debug, PartialEq, Eq)]
+pub struct Overflow;
+
+impl fmt::Display for Overflow {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        write!(f, "total overflowed")
+    }
+}
+
+impl std::error::Error for Overflow {}
+
+/// Why the answers couldn't be worked out from an input.
+#[derive(Debug, PartialEq, Eq)]
+pub enum InputError {
+    Parse {
+        /// 1-based line number in the input.
+        line: usize,
+        message: String,
+    },
+    Overflow,
+}
+
+impl From<Overflow> for InputError {
+    fn from(_: Overflow) -> Self {
+        InputError::Overflow
+    }
+}
+
+impl fmt::Display for InputError {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match self {
+            InputError::Parse { line, message } => write!(f, "line {}: {}", line, message),
+            InputError::Overflow => write!(f, "{}", Overflow),
+        }
+    }
+}
+
+impl std::error::Error for InputError {}
+
+/// Pairs up the smallest IDs in each list, then the second smallest and so on,
+/// and sums the distance between each pair.
+pub fn pair_distance<T: LocationId, M: DistanceMetric<T>>(
+    mut list1: Vec<T>,
+    mut list2: Vec<T>,
+    metric: &M,
+) -> Result<M::Output, Overflow> {
+    list1.sort_unstable();
+    list2.sort_unstable();
+
+    zip(list1, list2)
+        .try_fold(M::Output::default(), |total, (a, b)| {
+            total.checked_add(metric.distance(a, b)?)
+        })
+        .ok_or(Overflow)
+}
+
+fn parse_id<T: LocationId>(input: &str) -> IResult<&str, T> {
+    map_res(recognize(pair(opt(char('-')), digit1)), str::parse)(input)
+}
+
+/// Parses any number of whitespace separated columns, one list per column. Every
+/// row must have the same number of columns. Blank lines are skipped.
+fn parse_columns<T: LocationId>(input: &str) -> Result<Vec<Vec<T>>, InputError> {
+    let mut columns: Vec<Vec<T>> = Vec::new();
+    for (i, line) in input.lines().enumerate() {
+        let line = line.trim();
+        if line.is_empty() {
+            continue;
+        }
+        let error = |message| InputError::Parse {
+            line: i + 1,
+            message,
+        };
+        let (_, row) = all_consuming(separated_list1(space1, parse_id::<T>))(line)
+            .map_err(|_| error(format!("invalid location IDs {:?}", line)))?;
+        if columns.is_empty() {
+            columns.resize_with(row.len(), Vec::new);
+        } else if row.len() != columns.len() {
+            return Err(error(format!(
+                "expected {} columns, found {}",
+                columns.len(),
+                row.len()
+            )));
+        }
+        zip(&mut columns, row).for_each(|(column, id)| column.push(id));
+    }
+    Ok(columns)
+}
+
+fn parse_lists<T: LocationId>(input: &str) -> Result<(Vec<T>, Vec<T>), InputError> {
+    let columns = parse_columns(input)?;
+    match <[Vec<T>; 2]>::try_from(columns) {
+        Ok([list1, list2]) => Ok((list1, list2)),
+        Err(columns) => Err(InputError::Parse {
+            line: 1,
+            message: format!("expected 2 columns, found {}", columns.len()),
+        }),
+    }
+}
+
+fn count_ids<T: LocationId>(list: &[T]) -> HashMap<T, u64> {
+    list.iter().fold(HashMap::new(), |mut counts, &a| {
+        *counts.entry(a).or_insert(0) += 1;
+        counts
+    })
+}
+
+/// Sums each ID's magnitude times the number of times it appears in `counts`.
+fn similarity_score<T: LocationId>(
+    list: &[T],
+    loc_id_counts: &HashMap<T, u64>,
+) -> Result<u64, Overflow> {
+    list.iter()
+        .try_fold(0u64, |total, a| {
+            let count = loc_id_counts.get(a).copied().unwrap_or(0);
+            total.checked_add(a.magnitude().checked_mul(count)?)
+        })
+        .ok_or(Overflow)
+}
+
+pub fn process_part_one(input: &str) -> u64 {
+    process_part_one_with::<u32, _>(input, &Absolute).unwrap()
+}
+
+/// Part one with IDs parsed as `T` and distances measured by `metric`.
+pub fn process_part_one_with<T: LocationId, M: DistanceMetric<T>>(
+    input: &str,
+    metric: &M,
+) -> Result<M::Output, InputError> {
+    let (list1, list2) = parse_lists::<T>(input)?;
+    Ok(pair_distance(list1, list2, metric)?)
+}
+
+pub fn process_part_two(input: &str) -> u64 {
+    process_part_two_with::<u32>(input).unwrap()
+}
+
+/// Part two with IDs parsed as `T`. Negative IDs score their magnitude.
+pub fn process_part_two_with<T: LocationId>(input: &str) -> Result<u64, InputError> {
+    let (list1, list2) = parse_lists::<T>(input)?;
+
+    // Create a hashmap of counts in list2
+    let loc_id_counts = count_ids(&list2);
+    Ok(similarity_score(&list1, &loc_id_counts)?)
+}
+
+/// Both parts' answers between every pair of lists, for inputs with any number
+/// of columns.
+#[derive(Debug, PartialEq, Eq)]
+pub struct LocationMatrix {
+    /// `distances[i][j]` is the total distance between lists `i` and `j`.
+    pub distances: Vec<Vec<u64>>,
+    /// `similarities[i][j]` is the similarity score of list `i` against the
+    /// counts in list `j`, so it isn't symmetric.
+    pub similarities: Vec<Vec<u64>>,
+}
+
+/// The matrices for IDs parsed as `T`.
+pub fn pairwise_matrix<T: LocationId>(input: &str) -> Result<LocationMatrix, InputError> {
+    let lists = parse_columns::<T>(input)?;
+
+    let counts: Vec<HashMap<T, u64>> = lists.iter().map(|list| count_ids(list)).collect();
+    let similarities = lists
+        .iter()
+        .map(|list| counts.iter().map(|c| similarity_score(list, c)).collect())
+        .collect::<Result<_, _>>()?;
+
+    let distances = lists
+        .iter()
+        .map(|a| {
+            lists
+                .iter()
+                .map(|b| pair_distance(a.clone(), b.clone(), &Absolute))
+                .collect()
+        })
+        .collect::<Result<_, _>>()?;
+
+    Ok(LocationMatrix {
+        distances,
+        similarities,
+    })
+}
+
+#[derive(Debug, PartialEq, Eq, Clone, Copy)]
+pub enum Side {
+    Left,
+    Right,
+}
+
+impl Side {
+    fn index(self) -> usize {
+        match self {
+            Side::Left => 0,
+            Side::Right => 1,
+        }
+    }
+}
+
+#[derive(Debug, PartialEq, Eq, Clone, Copy)]
+pub struct Totals {
+    pub distance: u64,
+    pub similarity: u64,
+}
+
+/// Both lists kept sorted and counted, so that the total distance and
+/// similarity score can be updated as IDs are inserted and removed rather than
+/// recomputed. If the lists have different lengths the extra IDs in the longer
+/// one are left unpaired.
+#[derive(Debug)]
+pub struct LocationIndex<T: LocationId> {
+    sorted: [Vec<T>; 2],
+    counts: [HashMap<T, u64>; 2],
+    distance: u64,
+    similarity: u64,
+}
+
+impl<T: LocationId> Default for LocationIndex<T> {
+    fn default() -> Self {
+        LocationIndex {
+            sorted: [Vec::new(), Vec::new()],
+            counts: [HashMap::new(), HashMap::new()],
+            distance: 0,
+            similarity: 0,
+        }
+    }
+}
+
+impl<T: LocationId> LocationIndex<T> {
+    pub fn new() -> Self {
+        Self::default()
+    }
+
+    pub fn from_input(input: &str) -> Result<Self, InputError> {
+        let (list1, list2) = parse_lists::<T>(input)?;
+        let mut index = Self::new();
+        for id in list1 {
+            index.insert(Side::Left, id)?;
+        }
+        for id in list2 {
+            index.insert(Side::Right, id)?;
+        }
+        Ok(index)
+    }
+
+    pub fn totals(&self) -> Totals {
+        Totals {
+            distance: self.distance,
+            similarity: self.similarity,
+        }
+    }
+
+    pub fn sorted(&self, side: Side) -> &[T] {
+        &self.sorted[side.index()]
+    }
+
+    /// Distance over the pairs from `rank` onwards, the only ones that change
+    /// when an ID is inserted or removed at that rank.
+    fn tail_distance(&self, rank: usize) -> Result<u64, Overflow> {
+        let [left, right] = &self.sorted;
+        let tail = |list: &Vec<T>| list.get(rank..).unwrap_or(&[]).to_vec();
+        pair_distance(tail(left), tail(right), &Absolute)
+    }
+
+    /// Distance after the pairs from `rank` onwards changed from `old_tail`.
+    fn retotal(&self, rank: usize, old_tail: u64) -> Result<u64, Overflow> {
+        (self.distance - old_tail)
+            .checked_add(self.tail_distance(rank)?)
+            .ok_or(Overflow)
+    }
+
+    /// Adds `id` to one of the lists. If either total would overflow the index is
+    /// left as it was.
+    pub fn insert(&mut self, side: Side, id: T) -> Result<Totals, Overflow> {
+        let (this, other) = (side.index(), 1 - side.index());
+
+        let rank = self.sorted[this].partition_point(|&x| x < id);
+        let old_tail = self.tail_distance(rank)?;
+        self.sorted[this].insert(rank, id);
+        let count = self.counts[other].get(&id).copied().unwrap_or(0);
+        let totals = self.retotal(rank, old_tail).and_then(|distance| {
+            let similarity = id
+                .magnitude()
+                .checked_mul(count)
+                .and_then(|score| self.similarity.checked_add(score))
+                .ok_or(Overflow)?;
+            Ok((distance, similarity))
+        });
+        let Ok((distance, similarity)) = totals else {
+            self.sorted[this].remove(rank);
+            return Err(Overflow);
+        };
+        self.distance = distance;
+        self.similarity = similarity;
+
+        *self.counts[this].entry(id).or_insert(0) += 1;
+        Ok(self.totals())
+    }
+
+    /// Removes one occurrence of `id`, or returns `None` if it isn't in the list.
+    /// If the distance would overflow the index is left as it was.
+    pub fn remove(&mut self, side: Side, id: T) -> Result<Option<Totals>, Overflow> {
+        let (this, other) = (side.index(), 1 - side.index());
+
+        let Ok(rank) = self.sorted[this].binary_search(&id) else {
+            return Ok(None);
+        };
+        let old_tail = self.tail_distance(rank)?;
+        self.sorted[this].remove(rank);
+        self.distance = match self.retotal(rank, old_tail) {
+            Ok(distance) => distance,
+            Err(e) => {
+                self.sorted[this].insert(rank, id);
+                return Err(e);
+            }
+        };
+
+        let count = self.counts[this].get_mut(&id).unwrap();
+        *count -= 1;
+        if *count == 0 {
+            self.counts[this].remove(&id);
+        }
+        // Can't underflow, this ID's score was added when it was inserted
+        self.similarity -= id.magnitude() * self.counts[other].get(&id).unwrap_or(&0);
+
+        Ok(Some(self.totals()))
+    }
+}
+
+/// Intermediate results of both parts, printed by the binaries with `--explain`.
+#[derive(Debug)]
+pub struct Explanation<T: LocationId> {
+    pub sorted_list1: Vec<T>,
+    pub sorted_list2: Vec<T>,
+    /// Distance between each pair of sorted location IDs.
+    pub distances: Vec<u64>,
+    pub total_distance: u64,
+    /// Each location ID in the left list with the number of times it appears in
+    /// the right list and the score that gives it.
+    pub counts: Vec<(T, u64, u64)>,
+    pub similarity_score: u64,
+}
+
+impl<T: LocationId> fmt::Display for Explanation<T> {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        writeln!(f, "left\tright\tdistance")?;
+        for ((a, b), d) in zip(zip(&self.sorted_list1, &self.sorted_list2), &self.distances) {
+            writeln!(f, "{}\t{}\t{}", a, b, d)?;
+        }
+        writeln!(f, "total distance: {}", self.total_distance)?;
+
+        writeln!(f, "\nleft\tcount\tscore")?;
+        for (a, count, score) in &self.counts {
+            writeln!(f, "{}\t{}\t{}", a, count, score)?;
+        }
+        write!(f, "similarity score: {}", self.similarity_score)
+    }
+}
+
+/// The explanation for IDs parsed as `T`.
+pub fn explain<T: LocationId>(input: &str) -> Result<Explanation<T>, InputError> {
+    let (mut list1, mut list2) = parse_lists::<T>(input)?;
+    let loc_id_counts = count_ids(&list2);
+    let counts = list1
+        .iter()
+        .map(|&a| {
+            let count = loc_id_counts.get(&a).copied().unwrap_or(0);
+            let score = a.magnitude().checked_mul(count).ok_or(Overflow)?;
+            Ok((a, count, score))
+        })
+        .collect::<Result<_, Overflow>>()?;
+    let similarity_score = similarity_score(&list1, &loc_id_counts)?;
+    list1.sort_unstable();
+    list2.sort_unstable();
+    let distances = zip(&list1, &list2).map(|(&a, &b)| a.abs_diff(b)).collect();
+    let total_distance = pair_distance(list1.clone(), list2.clone(), &Absolute)?;
+
+    Ok(Explanation {
+        sorted_list1: list1,
+        sorted_list2: list2,
+        distances,
+        total_distance,
+        counts,
+        similarity_score,
+    })
+}
+
+/// How much one location ID adds to the similarity score.
+#[derive(Debug, PartialEq, Eq)]
+pub struct Contribution<T: LocationId> {
+    pub id: T,
+    pub left_count: u64,
+    pub right_count: u64,
+    /// `|id| * left_count * right_count`
+    pub score: u64,
+}
+
+#[derive(Debug, PartialEq)]
+pub struct Summary {
+    pub pairs: usize,
+    pub total_distance: u64,
+    pub mean_distance: f64,
+    pub median_distance: f64,
+    pub max_distance: u64,
+    pub similarity_score: u64,
+    /// Number of distinct location IDs found in both lists.
+    pub shared_ids: usize,
+}
+
+#[derive(Debug, PartialEq)]
+pub struct Report<T: LocationId> {
+    /// Each pair of sorted location IDs with the distance between them.
+    pub pairs: Vec<(T, T, u64)>,
+    /// Location IDs missing from the right list, with how often they appear in
+    /// the left one.
+    pub left_only: Vec<(T, u64)>,
+    /// Location IDs missing from the left list, with how often they appear in
+    /// the right one.
+    pub right_only: Vec<(T, u64)>,
+    /// The largest contributions to the similarity score, largest first.
+    pub top_contributors: Vec<Contribution<T>>,
+    pub summary: Summary,
+}
+
+impl<T: LocationId> Report<T> {
+    /// Writes every section as rows of `section,key,left,right,value`, with the
+    /// `left` and `right` columns holding what each list contributes:
+    ///
+    /// - `pair,,<left id>,<right id>,<distance>`
+    /// - `left_only,<id>,,,<count>`
+    /// - `right_only,<id>,,,<count>`
+    /// - `contributor,<id>,<left count>,<right count>,<score>`
+    /// - `summary,<statistic>,,,<value>`
+    pub fn to_csv(&self) -> String {
+        let mut csv = String::from("section,key,left,right,value\n");
+        for (a, b, d) in &self.pairs {
+            csv += &format!("pair,,{},{},{}\n", a, b, d);
+        }
+        for (id, count) in &self.left_only {
+            csv += &format!("left_only,{},,,{}\n", id, count);
+        }
+        for (id, count) in &self.right_only {
+            csv += &format!("right_only,{},,,{}\n", id, count);
+        }
+        for c in &self.top_contributors {
+            csv += &format!(
+                "contributor,{},{},{},{}\n",
+                c.id, c.left_count, c.right_count, c.score
+            );
+        }
+        for (name, value) in self.summary.statistics() {
+            csv += &format!("summary,{},,,{}\n", name, value);
+        }
+        csv
+    }
+}
+
+impl Summary {
+    fn statistics(&self) -> [(&'static str, String); 7] {
+        [
+            ("pairs", self.pairs.to_string()),
+            ("total_distance", self.total_distance.to_string()),
+            ("mean_distance", self.mean_distance.to_string()),
+            ("median_distance", self.median_distance.to_string()),
+            ("max_distance", self.max_distance.to_string()),
+            ("similarity_score", self.similarity_score.to_string()),
+            ("shared_ids", self.shared_ids.to_string()),
+        ]
+    }
+}
+
+impl<T: LocationId> fmt::Display for Report<T> {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        writeln!(f, "left\tright\tdistance")?;
+        for (a, b, d) in &self.pairs {
+            writeln!(f, "{}\t{}\t{}", a, b, d)?;
+        }
+
+        writeln!(f, "\nonly in left list")?;
+        for (id, count) in &self.left_only {
+            writeln!(f, "{} (x{})", id, count)?;
+        }
+        writeln!(f, "\nonly in right list")?;
+        for (id, count) in &self.right_only {
+            writeln!(f, "{} (x{})", id, count)?;
+        }
+
+        writeln!(f, "\nid\tleft\tright\tscore")?;
+        for c in &self.top_contributors {
+            writeln!(
+                f,
+                "{}\t{}\t{}\t{}",
+                c.id, c.left_count, c.right_count, c.score
+            )?;
+        }
+
+        writeln!(f)?;
+        for (name, value) in self.summary.statistics() {
+            writeln!(f, "{}: {}", name.replace('_', " "), value)?;
+        }
+        Ok(())
+    }
+}
+
+/// Builds a report for IDs parsed as `T`, keeping the `top` largest
+/// contributions to the similarity score.
+pub fn report<T: LocationId>(input: &str, top: usize) -> Result<Report<T>, InputError> {
+    let (mut list1, mut list2) = parse_lists::<T>(input)?;
+    list1.sort_unstable();
+    list2.sort_unstable();
+    let counts1 = count_ids(&list1);
+    let counts2 = count_ids(&list2);
+
+    let pairs: Vec<_> = zip(&list1, &list2)
+        .map(|(&a, &b)| (a, b, a.abs_diff(b)))
+        .collect();
+
+    let only_in = |counts: &HashMap<T, u64>, other: &HashMap<T, u64>| {
+        let mut ids: Vec<_> = counts
+            .iter()
+            .filter(|(id, _)| !other.contains_key(id))
+            .map(|(&id, &count)| (id, count))
+            .collect();
+        ids.sort_unstable();
+        ids
+    };
+    let left_only = only_in(&counts1, &counts2);
+    let right_only = only_in(&counts2, &counts1);
+
+    let mut contributors: Vec<_> = counts1
+        .iter()
+        .filter_map(|(&id, &left_count)| {
+            let right_count = *counts2.get(&id)?;
+            let score = id
+                .magnitude()
+                .checked_mul(left_count)
+                .and_then(|score| score.checked_mul(right_count))
+                .ok_or(Overflow);
+            Some(score.map(|score| Contribution {
+                id,
+                left_count,
+                right_count,
+                score,
+            }))
+        })
+        .collect::<Result<_, _>>()?;
+    contributors.sort_unstable_by(|a, b| b.score.cmp(&a.score).then(a.id.cmp(&b.id)));
+    let shared_ids = contributors.len();
+    let similarity_score = contributors
+        .iter()
+        .try_fold(0u64, |total, c| total.checked_add(c.score))
+        .ok_or(Overflow)?;
+    contributors.truncate(top);
+
+    let total_distance = pair_distance(list1, list2, &Absolute)?;
+    let mut distances: Vec<_> = pairs.iter().map(|(_, _, d)| *d).collect();
+    distances.sort_unstable();
+    let n = distances.len();
+    let (mean_distance, median_distance) = if n == 0 {
+        (0.0, 0.0)
+    } else if n % 2 == 1 {
+        (total_distance as f64 / n as f64, distances[n / 2] as f64)
+    } else {
+        (
+            total_distance as f64 / n as f64,
+            (distances[n / 2 - 1] as f64 + distances[n / 2] as f64) / 2.0,
+        )
+    };
+
+    Ok(Report {
+        summary: Summary {
+            pairs: n,
+            total_distance,
+            mean_distance,
+            median_distance,
+            max_distance: distances.last().copied().unwrap_or(0),
+            similarity_score,
+            shared_ids,
+        },
+        pairs,
+        left_only,
+        right_only,
+        top_contributors: contributors,
+    })
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    const TEST_INPUT: &str = "3   4
+4   3
+2   5
+1   3
+3   9
+3   3";
+
+    #[test]
+    fn test_part_one() {
+        assert_eq!(process_part_one(TEST_INPUT), 11)
+    }
+
+    #[test]
+    fn test_part_two() {
+        assert_eq!(process_part_two(TEST_INPUT), 31)
+    }
+
+    #[test]
+    fn test_part_two_wide_ids() {
+        assert_eq!(process_part_two_with::<i64>("-3   3\n5   -3"), Ok(3));
+        assert_eq!(
+            process_part_two_with::<u64>("4294967296   4294967296\n1   4294967296"),
+            Ok(8589934592)
+        );
+        let input = "4294967295   4294967295\n".repeat(5);
+        assert_eq!(
+            process_part_two_with::<u32>(input.trim_end()),
+            Ok(4294967295 * 25)
+        );
+        assert_eq!(
+            process_part_two_with::<u64>(
+                "18446744073709551615   18446744073709551615\n1   18446744073709551615"
+            ),
+            Err(InputError::Overflow)
+        )
+    }
+
+    #[test]
+    fn test_part_one_metrics() {
+        assert_eq!(
+            process_part_one_with::<u64, _>(TEST_INPUT, &Squared),
+            Ok(35)
+        );
+        assert_eq!(
+            process_part_one_with::<u32, _>(TEST_INPUT, &Capped(1)),
+            Ok(5)
+        );
+        let relative = process_part_one_with::<i64, _>(TEST_INPUT, &Relative).unwrap();
+        assert!(
+            (relative - (2.0 / 3.0 + 1.0 / 3.0 + 1.0 / 4.0 + 2.0 / 5.0 + 5.0 / 9.0)).abs() < 1e-9
+        )
+    }
+
+    #[test]
+    fn test_part_one_wide_ids() {
+        assert_eq!(
+            process_part_one_with::<i64, _>("-5   3\n7   -2", &Absolute),
+            Ok(7)
+        );
+        assert_eq!(
+            process_part_one_with::<u64, _>("4294967296   0\n0   4294967296", &Absolute),
+            Ok(0)
+        );
+        assert_eq!(
+            process_part_one_with::<u64, _>("0   4294967296\n0   4294967296", &Absolute),
+            Ok(8589934592)
+        );
+        assert_eq!(
+            process_part_one_with::<u64, _>("0   4294967296", &Squared),
+            Err(InputError::Overflow)
+        );
+        assert_eq!(
+            process_part_one_with::<i64, _>(
+                "-9223372036854775808   9223372036854775807\n0   9223372036854775807",
+                &Absolute
+            ),
+            Err(InputError::Overflow)
+        )
+    }
+
+    #[test]
+    fn test_pairwise_matrix() {
+        let matrix = pairwise_matrix::<u32>("3 4 1\n4 3 2\n2 5 3").unwrap();
+        assert_eq!(
+            matrix,
+            LocationMatrix {
+                distances: vec![vec![0, 3, 3], vec![3, 0, 6], vec![3, 6, 0]],
+                similarities: vec![vec![9, 7, 5], vec![7, 12, 3], vec![5, 3, 6]],
+            }
+        );
+        let matrix = pairwise_matrix::<u32>(TEST_INPUT).unwrap();
+        assert_eq!(matrix.distances[0][1], 11);
+        assert_eq!(matrix.similarities[0][1], 31);
+        assert_eq!(pairwise_matrix::<u32>("4294967295 4294967295\n4294967295 4294967295\n4294967295 4294967295\n4294967295 4294967295\n4294967295 4294967295"), Ok(LocationMatrix {
+            distances: vec![vec![0; 2]; 2],
+            similarities: vec![vec![4294967295 * 25; 2]; 2],
+        }))
+    }
+
+    #[test]
+    fn test_parse_columns_rejects_ragged_rows() {
+        assert_eq!(
+            parse_columns::<u32>("1 2 3\n4 5"),
+            Err(InputError::Parse {
+                line: 2,
+                message: "expected 3 columns, found 2".to_string()
+            })
+        );
+        assert_eq!(
+            pairwise_matrix::<u32>("1 2 3\n4 5")
+                .unwrap_err()
+                .to_string(),
+            "line 2: expected 3 columns, found 2"
+        );
+        assert!(matches!(
+            pairwise_matrix::<u32>("1 2\n\n3 x"),
+            Err(InputError::Parse { line: 3, .. })
+        ));
+        assert!(matches!(
+            process_part_one_with::<u32, _>("1 2 3\n4 5 6", &Absolute),
+            Err(InputError::Parse { line: 1, .. })
+        ));
+        assert!(matches!(
+            report::<u32>("-1   2", 1),
+            Err(InputError::Parse { line: 1, .. })
+        ));
+        assert_eq!(
+            pairwise_matrix::<i64>("-1 1\n1 -1\n"),
+            Ok(LocationMatrix {
+                distances: vec![vec![0, 0], vec![0, 0]],
+                similarities: vec![vec![2, 2], vec![2, 2]],
+            })
+        )
+    }
+
+    #[test]
+    fn test_location_index() {
+        let mut index = LocationIndex::<u32>::from_input(TEST_INPUT).unwrap();
+        assert_eq!(
+            index.totals(),
+            Totals {
+                distance: 11,
+                similarity: 31
+            }
+        );
+
+        // Compare every update against recomputing from scratch
+        let updates = [
+            (Side::Left, 9, true),
+            (Side::Right, 3, false),
+            (Side::Right, 1, true),
+            (Side::Left, 3, false),
+            (Side::Left, 3, false),
+            (Side::Right, 4, true),
+        ];
+        for (side, id, insert) in updates {
+            let totals = if insert {
+                index.insert(side, id).unwrap()
+            } else {
+                index.remove(side, id).unwrap().unwrap()
+            };
+            let (left, right) = (index.sorted(Side::Left), index.sorted(Side::Right));
+            assert_eq!(
+                Ok(totals.distance),
+                pair_distance(left.to_vec(), right.to_vec(), &Absolute)
+            );
+            assert_eq!(
+                Ok(totals.similarity),
+                similarity_score(left, &count_ids(right))
+            );
+        }
+
+        assert_eq!(index.remove(Side::Left, 42), Ok(None));
+
+        // An insert that would overflow leaves the index unchanged
+        let mut index = LocationIndex::<u32>::from_input("1   3\n2   3").unwrap();
+        index.similarity = u64::MAX - 5;
+        let before = index.totals();
+        assert_eq!(index.insert(Side::Left, 3), Err(Overflow));
+        assert_eq!(index.sorted(Side::Left), &[1, 2]);
+        assert_eq!(index.totals(), before);
+        assert_eq!(
+            index.insert(Side::Left, 2).unwrap().similarity,
+            u64::MAX - 5
+        );
+
+        let mut index = LocationIndex::<i64>::from_input("-5   3\n7   -2").unwrap();
+        assert_eq!(index.totals().distance, 7);
+        assert_eq!(index.insert(Side::Right, -5).unwrap().similarity, 5);
+        assert_eq!(index.sorted(Side::Right), &[-5, -2, 3]);
+        assert_eq!(
+            index.remove(Side::Left, -5).unwrap(),
+            Some(Totals {
+                distance: 12,
+                similarity: 0
+            })
+        )
+    }
+
+    #[test]
+    fn test_explain() {
+        let explanation = explain::<u32>(TEST_INPUT).unwrap();
+        assert_eq!(explanation.sorted_list1, vec![1, 2, 3, 3, 3, 4]);
+        assert_eq!(explanation.distances, vec![2, 1, 0, 1, 2, 5]);
+        assert_eq!(explanation.counts[0], (3, 3, 9));
+        assert!(explanation.to_string().ends_with("similarity score: 31"));
+
+        let explanation = explain::<i64>("-4294967296   5\n7   -4294967296").unwrap();
+        assert_eq!(explanation.sorted_list1, vec![-4294967296, 7]);
+        assert_eq!(explanation.distances, vec![0, 2]);
+        assert_eq!(explanation.counts[0], (-4294967296, 1, 4294967296));
+        assert_eq!(explanation.similarity_score, 4294967296)
+    }
+
+    #[test]
+    fn test_report() {
+        let report = report::<u32>(TEST_INPUT, 1).unwrap();
+        assert_eq!(
+            report.pairs,
+            vec![
+                (1, 3, 2),
+                (2, 3, 1),
+                (3, 3, 0),
+                (3, 4, 1),
+                (3, 5, 2),
+                (4, 9, 5)
+            ]
+        );
+        assert_eq!(report.left_only, vec![(1, 1), (2, 1)]);
+        assert_eq!(report.right_only, vec![(5, 1), (9, 1)]);
+        assert_eq!(
+            report.top_contributors,
+            vec![Contribution {
+                id: 3,
+                left_count: 3,
+                right_count: 3,
+                score: 27
+            }]
+        );
+        assert_eq!(report.summary.total_distance, 11);
+        assert_eq!(report.summary.median_distance, 1.5);
+        assert_eq!(report.summary.max_distance, 5);
+        assert_eq!(report.summary.similarity_score, 31);
+        assert_eq!(report.summary.shared_ids, 2);
+
+        let csv = report.to_csv();
+        let lines: Vec<_> = csv.lines().collect();
+        assert_eq!(lines[0], "section,key,left,right,value");
+        assert_eq!(lines[1], "pair,,1,3,2");
+        assert!(lines.contains(&"left_only,1,,,1"));
+        assert!(lines.contains(&"right_only,9,,,1"));
+        assert!(lines.contains(&"contributor,3,3,3,27"));
+        assert!(lines.contains(&"summary,similarity_score,,,31"));
+        assert!(lines.iter().all(|line| line.split(',').count() == 5));
+
+        // Different counts on each side land in their own columns
+        let uneven_csv = super::report::<u32>("3   3\n4   3", 1).unwrap().to_csv();
+        let uneven_lines: Vec<_> = uneven_csv.lines().collect();
+        assert!(uneven_lines.contains(&"contributor,3,1,2,6"));
+        assert!(uneven_lines.contains(&"left_only,4,,,1"));
+        assert!(uneven_lines.contains(&"summary,similarity_score,,,6"));
+
+        let wide = super::report::<u64>("4294967296   4294967296\n1   4294967297", 1).unwrap();
+        assert_eq!(
+            wide.pairs,
+            vec![(1, 4294967296, 4294967295), (4294967296, 4294967297, 1)]
+        );
+        assert_eq!(wide.summary.max_distance, 4294967295);
+        assert_eq!(wide.top_contributors[0].score, 4294967296)
+    }
+}
@@ -0,0 +1,23 @@
+use aoc_config::CONFIG_FILE;
+use day_02::{explain_with_policy, process_part_one_with_policy, SafetyPolicy};
+use std::{env, fs};
+
+fn main() {
+    let input = fs::read_to_string("input.txt").unwrap();
+    let mut policy: SafetyPolicy =
+        aoc_config::load(CONFIG_FILE).expect("error loading the config file");
+    let explain = env::args().any(|arg| arg == "--explain");
+    policy
+        .apply_args(env::args().skip(1).filter(|arg| arg != "--explain"))
+        .unwrap_or_else(|e| panic!("{}", e));
+    // Part one never applies the Problem Dampener
+    let policy = SafetyPolicy {
+        max_removals: 0,
+        ..policy
+    };
+    let answer = process_part_one_with_policy(&input, &policy).unwrap_or_else(|e| panic!("{}", e));
+    println!("{}", answer);
+    if explain {
+        print!("{}", explain_with_policy(&input, &policy).unwrap())
+    }
+}
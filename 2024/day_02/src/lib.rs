@@ -0,0 +1,599 @@
+#[cfg(feature = "parallel")]
+use rayon::prelude::*;
+
+use aoc_config::Validate;
+use serde::Deserialize;
+use std::{fmt, str::FromStr};
+
+#[derive(Debug, PartialEq, Eq, Clone, Copy, Deserialize)]
+#[serde(rename_all = "lowercase")]
+pub enum Direction {
+    /// Either direction, as long as the whole report agrees.
+    Any,
+    Increasing,
+    Decreasing,
+}
+
+impl FromStr for Direction {
+    type Err = String;
+
+    fn from_str(s: &str) -> Result<Self, Self::Err> {
+        match s {
+            "any" => Ok(Direction::Any),
+            "increasing" => Ok(Direction::Increasing),
+            "decreasing" => Ok(Direction::Decreasing),
+            _ => Err(format!("unknown direction {}", s)),
+        }
+    }
+}
+
+/// What makes a report safe. The default is the puzzle's rule: all levels
+/// increasing or all decreasing, by at least 1 and at most 3 at a time.
+#[derive(Debug, Clone, Copy, Deserialize)]
+#[serde(default, deny_unknown_fields)]
+pub struct SafetyPolicy {
+    pub min_step: u32,
+    pub max_step: u32,
+    /// Whether two equal adjacent levels are allowed. Plateaus don't count
+    /// towards the direction of the report.
+    pub allow_plateaus: bool,
+    pub direction: Direction,
+    /// How many levels the Problem Dampener can remove in part two.
+    pub max_removals: usize,
+}
+
+impl Default for SafetyPolicy {
+    fn default() -> Self {
+        SafetyPolicy {
+            min_step: 1,
+            max_step: 3,
+            allow_plateaus: false,
+            direction: Direction::Any,
+            max_removals: 1,
+        }
+    }
+}
+
+impl Validate for SafetyPolicy {
+    fn validate(&self) -> Result<(), String> {
+        if self.min_step > self.max_step {
+            return Err("min_step must not be greater than max_step".to_string());
+        }
+        Ok(())
+    }
+}
+
+impl SafetyPolicy {
+    /// Overrides the policy with `--min-step <n>`, `--max-step <n>`,
+    /// `--allow-plateaus`, `--direction <any|increasing|decreasing>` and
+    /// `--max-removals <n>`.
+    pub fn apply_args<I: IntoIterator<Item = String>>(&mut self, args: I) -> Result<(), String> {
+        let mut args = args.into_iter();
+        while let Some(flag) = args.next() {
+            if flag == "--allow-plateaus" {
+                self.allow_plateaus = true;
+                continue;
+            }
+            let value = args
+                .next()
+                .ok_or_else(|| format!("missing value for {}", flag))?;
+            match flag.as_str() {
+                "--min-step" => self.min_step = value.parse().map_err(|_| "invalid min step")?,
+                "--max-step" => self.max_step = value.parse().map_err(|_| "invalid max step")?,
+                "--direction" => self.direction = value.parse()?,
+                "--max-removals" => {
+                    self.max_removals = value.parse().map_err(|_| "invalid max removals")?
+                }
+                _ => return Err(format!("unrecognised argument {}", flag)),
+            }
+        }
+        self.validate()
+    }
+}
+
+#[derive(Debug, PartialEq, Eq, Clone, Copy)]
+pub enum Violation {
+    /// The report started going one way, then turned around.
+    DirectionChange,
+    /// The report goes against the policy's required direction.
+    WrongDirection,
+    StepTooLarge,
+    StepTooSmall,
+    ZeroStep,
+}
+
+impl fmt::Display for Violation {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        let reason = match self {
+            Violation::DirectionChange => "direction change",
+            Violation::WrongDirection => "wrong direction",
+            Violation::StepTooLarge => "step too large",
+            Violation::StepTooSmall => "step too small",
+            Violation::ZeroStep => "zero step",
+        };
+        write!(f, "{}", reason)
+    }
+}
+
+/// Index of the first level that breaks the policy, compared to the level before
+/// it, and the rule it breaks.
+pub fn first_violation(nums: &[i32], policy: &SafetyPolicy) -> Option<(usize, Violation)> {
+    let mut direction = policy.direction;
+    nums.windows(2).enumerate().find_map(|(i, p)| {
+        let step = p[1] as i64 - p[0] as i64;
+        let violation = if step == 0 {
+            (!policy.allow_plateaus).then_some(Violation::ZeroStep)
+        } else {
+            let step_direction = if step > 0 {
+                Direction::Increasing
+            } else {
+                Direction::Decreasing
+            };
+            if direction == Direction::Any {
+                direction = step_direction;
+            }
+            if direction != step_direction {
+                Some(match policy.direction {
+                    Direction::Any => Violation::DirectionChange,
+                    _ => Violation::WrongDirection,
+                })
+            } else if step.unsigned_abs() > policy.max_step as u64 {
+                Some(Violation::StepTooLarge)
+            } else if step.unsigned_abs() < policy.min_step as u64 {
+                Some(Violation::StepTooSmall)
+            } else {
+                None
+            }
+        };
+        violation.map(|violation| (i + 1, violation))
+    })
+}
+
+pub fn is_report_safe(nums: &[i32], policy: &SafetyPolicy) -> bool {
+    first_violation(nums, policy).is_none()
+}
+
+#[derive(Debug, PartialEq, Eq, Clone)]
+pub enum ReportStatus {
+    Safe,
+    /// Safe once the Problem Dampener removes the levels at these indices.
+    Rescued(Vec<usize>),
+    /// Unsafe even with the Problem Dampener. Holds the first level that breaks
+    /// the policy and why.
+    Unsafe {
+        index: usize,
+        reason: Violation,
+    },
+}
+
+/// Fewest levels to remove from `nums` to make it safe when they all have to go
+/// in `direction`, as long as that's no more than `max_removals`.
+fn removals_in_direction(
+    nums: &[i32],
+    policy: &SafetyPolicy,
+    direction: Direction,
+    max_removals: usize,
+) -> Option<Vec<usize>> {
+    let n = nums.len();
+    if n <= 1 {
+        return Some(Vec::new());
+    }
+    let policy = SafetyPolicy {
+        direction,
+        ..*policy
+    };
+
+    // For each level, the fewest removals from the levels up to it that leave it
+    // as the last level kept, and the kept level before it. Only the previous
+    // `max_removals + 1` levels can come before it without removing too many.
+    // Ties go to the later level, so earlier levels get removed first.
+    let mut best: Vec<Option<(usize, Option<usize>)>> = vec![None; n];
+    for i in 0..n {
+        let mut cost = (i <= max_removals).then_some((i, None));
+        for j in i.saturating_sub(max_removals + 1)..i {
+            let Some((removed, _)) = best[j] else {
+                continue;
+            };
+            let removed = removed + i - j - 1;
+            if removed <= max_removals
+                && cost.is_none_or(|(c, _)| removed <= c)
+                && is_report_safe(&[nums[j], nums[i]], &policy)
+            {
+                cost = Some((removed, Some(j)));
+            }
+        }
+        best[i] = cost;
+    }
+
+    let mut last = None;
+    for (i, cost) in best.iter().enumerate() {
+        if let Some((removed, _)) = cost {
+            let removed = removed + n - 1 - i;
+            if removed <= max_removals && last.is_none_or(|(_, r)| removed <= r) {
+                last = Some((i, removed));
+            }
+        }
+    }
+
+    let (mut i, _) = last?;
+    let mut kept = vec![false; n];
+    loop {
+        kept[i] = true;
+        match best[i] {
+            Some((_, Some(j))) => i = j,
+            _ => break,
+        }
+    }
+    Some((0..n).filter(|&i| !kept[i]).collect())
+}
+
+/// Indices of the fewest levels to remove to make the report safe, or `None` if
+/// that takes more than `max_removals`. Runs in O(n·k) for `k` removals.
+pub fn levels_to_remove(
+    nums: &[i32],
+    policy: &SafetyPolicy,
+    max_removals: usize,
+) -> Option<Vec<usize>> {
+    let directions = match policy.direction {
+        Direction::Any => vec![Direction::Increasing, Direction::Decreasing],
+        direction => vec![direction],
+    };
+    directions
+        .into_iter()
+        .filter_map(|direction| removals_in_direction(nums, policy, direction, max_removals))
+        .min_by_key(|removed| removed.len())
+}
+
+/// Number of levels that have to be removed to make the report safe.
+pub fn min_removals(nums: &[i32], policy: &SafetyPolicy) -> usize {
+    levels_to_remove(nums, policy, nums.len())
+        .expect("a single level is always safe")
+        .len()
+}
+
+pub fn diagnose(nums: &[i32], policy: &SafetyPolicy) -> ReportStatus {
+    match levels_to_remove(nums, policy, policy.max_removals) {
+        Some(removed) if removed.is_empty() => ReportStatus::Safe,
+        Some(removed) => ReportStatus::Rescued(removed),
+        None => {
+            let (index, reason) =
+                first_violation(nums, policy).expect("a safe report needs no removals");
+            ReportStatus::Unsafe { index, reason }
+        }
+    }
+}
+
+/// The report's levels, with a line underneath marking the levels at fault:
+/// `^` under the first pair of levels that breaks the policy for an unsafe
+/// report, and `-` under the levels removed by the Problem Dampener.
+pub fn highlight(nums: &[i32], status: &ReportStatus) -> String {
+    let levels: Vec<String> = nums.iter().map(|n| n.to_string()).collect();
+    let marker = |i: usize| match status {
+        ReportStatus::Safe => ' ',
+        ReportStatus::Rescued(removed) if removed.contains(&i) => '-',
+        ReportStatus::Rescued(_) => ' ',
+        ReportStatus::Unsafe { index, .. } if i + 1 == *index || i == *index => '^',
+        ReportStatus::Unsafe { .. } => ' ',
+    };
+    let markers: Vec<String> = levels
+        .iter()
+        .enumerate()
+        .map(|(i, level)| marker(i).to_string().repeat(level.len()))
+        .collect();
+    format!("{}\n{}", levels.join(" "), markers.join(" ").trim_end())
+}
+
+#[derive(Debug, PartialEq, Eq)]
+pub struct ParseError {
+    /// 1-based line number in the input.
+    pub line: usize,
+    pub message: String,
+}
+
+impl fmt::Display for ParseError {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        write!(f, "line {}: {}", self.line, self.message)
+    }
+}
+
+impl std::error::Error for ParseError {}
+
+/// Parses one report per line, with levels separated by any whitespace. Blank
+/// lines are skipped.
+pub fn parse_reports(input: &str) -> Result<Vec<Vec<i32>>, ParseError> {
+    input
+        .lines()
+        .enumerate()
+        .filter(|(_, line)| !line.trim().is_empty())
+        .map(|(i, line)| {
+            line.split_whitespace()
+                .map(|n| {
+                    n.parse().map_err(|e| ParseError {
+                        line: i + 1,
+                        message: format!("invalid level {:?}: {}", n, e),
+                    })
+                })
+                .collect()
+        })
+        .collect()
+}
+
+fn count_safe(reports: &[Vec<i32>], is_safe: impl Fn(&[i32]) -> bool + Sync) -> usize {
+    #[cfg(feature = "parallel")]
+    let reports = reports.par_iter();
+    #[cfg(not(feature = "parallel"))]
+    let reports = reports.iter();
+    reports.filter(|nums| is_safe(nums)).count()
+}
+
+pub fn process_part_one(input: &str) -> usize {
+    process_part_one_with_policy(input, &SafetyPolicy::default()).unwrap()
+}
+
+pub fn process_part_one_with_policy(
+    input: &str,
+    policy: &SafetyPolicy,
+) -> Result<usize, ParseError> {
+    let reports = parse_reports(input)?;
+    Ok(count_safe(&reports, |nums| is_report_safe(nums, policy)))
+}
+
+pub fn process_part_two(input: &str) -> usize {
+    process_part_two_with_policy(input, &SafetyPolicy::default()).unwrap()
+}
+
+pub fn process_part_two_with_policy(
+    input: &str,
+    policy: &SafetyPolicy,
+) -> Result<usize, ParseError> {
+    let reports = parse_reports(input)?;
+    Ok(count_safe(&reports, |nums| {
+        levels_to_remove(nums, policy, policy.max_removals).is_some()
+    }))
+}
+
+/// Status of every report, printed by the binaries with `--explain`.
+#[derive(Debug)]
+pub struct Explanation {
+    pub reports: Vec<(Vec<i32>, ReportStatus)>,
+}
+
+impl fmt::Display for Explanation {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        for (nums, status) in &self.reports {
+            let summary = match status {
+                ReportStatus::Safe => "safe".to_string(),
+                ReportStatus::Rescued(removed) => {
+                    let removed: Vec<String> = removed
+                        .iter()
+                        .map(|&i| format!("{} ({})", i + 1, nums[i]))
+                        .collect();
+                    format!("safe by removing level {}", removed.join(", "))
+                }
+                ReportStatus::Unsafe { index, reason } => {
+                    format!(
+                        "unsafe, {} at level {} ({})",
+                        reason,
+                        index + 1,
+                        nums[*index]
+                    )
+                }
+            };
+            let view = highlight(nums, status);
+            let (levels, markers) = view.split_once('\n').unwrap();
+            writeln!(f, "{}: {}", levels, summary)?;
+            if !markers.is_empty() {
+                writeln!(f, "{}", markers)?;
+            }
+        }
+        Ok(())
+    }
+}
+
+pub fn explain(input: &str) -> Explanation {
+    explain_with_policy(input, &SafetyPolicy::default()).unwrap()
+}
+
+pub fn explain_with_policy(input: &str, policy: &SafetyPolicy) -> Result<Explanation, ParseError> {
+    let reports = parse_reports(input)?
+        .into_iter()
+        .map(|nums| {
+            let status = diagnose(&nums, policy);
+            (nums, status)
+        })
+        .collect();
+
+    Ok(Explanation { reports })
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    const TEST_INPUT: &str = "7 6 4 2 1
+1 2 7 8 9
+9 7 6 2 1
+1 3 2 4 5
+8 6 4 4 1
+1 3 6 7 9";
+
+    #[test]
+    fn test_part_one() {
+        assert_eq!(process_part_one(TEST_INPUT), 2)
+    }
+
+    #[test]
+    fn test_part_two() {
+        assert_eq!(process_part_two(TEST_INPUT), 4)
+    }
+
+    #[test]
+    fn test_policy() {
+        let strict = SafetyPolicy {
+            direction: Direction::Decreasing,
+            ..SafetyPolicy::default()
+        };
+        assert_eq!(process_part_one_with_policy(TEST_INPUT, &strict), Ok(1));
+        assert_eq!(process_part_two_with_policy(TEST_INPUT, &strict), Ok(2));
+
+        let lenient = SafetyPolicy {
+            max_step: 4,
+            allow_plateaus: true,
+            ..SafetyPolicy::default()
+        };
+        assert_eq!(process_part_one_with_policy(TEST_INPUT, &lenient), Ok(4));
+        assert!(is_report_safe(&[1, 1, 2, 2, 3], &lenient));
+        assert!(!is_report_safe(&[1, 1, 2, 1], &lenient))
+    }
+
+    #[test]
+    fn test_min_removals() {
+        let policy = SafetyPolicy::default();
+        let removals: Vec<usize> = parse_reports(TEST_INPUT)
+            .unwrap()
+            .iter()
+            .map(|nums| min_removals(nums, &policy))
+            .collect();
+        assert_eq!(removals, vec![0, 2, 2, 1, 1, 0]);
+
+        assert_eq!(levels_to_remove(&[9, 1, 2, 9, 3, 4], &policy, 1), None);
+        assert_eq!(
+            levels_to_remove(&[9, 1, 2, 9, 3, 4], &policy, 2),
+            Some(vec![0, 3])
+        );
+        assert_eq!(levels_to_remove(&[5, 1, 2, 3], &policy, 1), Some(vec![0]));
+        assert_eq!(levels_to_remove(&[1, 2, 3, 9], &policy, 1), Some(vec![3]));
+
+        let dampened = SafetyPolicy {
+            max_removals: 2,
+            ..policy
+        };
+        assert_eq!(process_part_two_with_policy(TEST_INPUT, &dampened), Ok(6))
+    }
+
+    #[test]
+    fn test_apply_args() {
+        let args = |s: &str| s.split(' ').map(String::from).collect::<Vec<_>>();
+        let mut policy = SafetyPolicy::default();
+        policy
+            .apply_args(args("--max-step 5 --allow-plateaus --direction increasing"))
+            .unwrap();
+        assert_eq!(policy.min_step, 1);
+        assert_eq!(policy.max_step, 5);
+        assert!(policy.allow_plateaus);
+        assert_eq!(policy.direction, Direction::Increasing);
+
+        assert!(policy.apply_args(args("--min-step 6")).is_err());
+        assert!(policy.apply_args(args("--direction sideways")).is_err());
+        assert!(policy.apply_args(args("--max-step")).is_err());
+        assert!(policy.apply_args(args("--verbose 1")).is_err())
+    }
+
+    #[test]
+    fn test_explain() {
+        let statuses: Vec<ReportStatus> = explain(TEST_INPUT)
+            .reports
+            .into_iter()
+            .map(|(_, status)| status)
+            .collect();
+        assert_eq!(
+            statuses,
+            vec![
+                ReportStatus::Safe,
+                ReportStatus::Unsafe {
+                    index: 2,
+                    reason: Violation::StepTooLarge
+                },
+                ReportStatus::Unsafe {
+                    index: 3,
+                    reason: Violation::StepTooLarge
+                },
+                ReportStatus::Rescued(vec![1]),
+                ReportStatus::Rescued(vec![2]),
+                ReportStatus::Safe
+            ]
+        );
+
+        let part_one = SafetyPolicy {
+            max_removals: 0,
+            ..SafetyPolicy::default()
+        };
+        let explanation = explain_with_policy(TEST_INPUT, &part_one).unwrap();
+        assert!(explanation
+            .reports
+            .iter()
+            .all(|(_, status)| !matches!(status, ReportStatus::Rescued(_))));
+        let safe = explanation
+            .reports
+            .iter()
+            .filter(|(_, status)| *status == ReportStatus::Safe)
+            .count();
+        assert_eq!(safe, process_part_one(TEST_INPUT))
+    }
+
+    #[test]
+    fn test_diagnose() {
+        let policy = SafetyPolicy {
+            max_removals: 0,
+            ..SafetyPolicy::default()
+        };
+        assert_eq!(
+            diagnose(&[1, 3, 2, 4, 5], &policy),
+            ReportStatus::Unsafe {
+                index: 2,
+                reason: Violation::DirectionChange
+            }
+        );
+        assert_eq!(
+            diagnose(&[8, 6, 4, 4, 1], &policy),
+            ReportStatus::Unsafe {
+                index: 3,
+                reason: Violation::ZeroStep
+            }
+        );
+        let increasing = SafetyPolicy {
+            direction: Direction::Increasing,
+            min_step: 2,
+            ..policy
+        };
+        assert_eq!(
+            first_violation(&[7, 6, 4, 2, 1], &increasing),
+            Some((1, Violation::WrongDirection))
+        );
+        assert_eq!(
+            first_violation(&[1, 3, 6, 7, 9], &increasing),
+            Some((3, Violation::StepTooSmall))
+        )
+    }
+
+    #[test]
+    fn test_highlight() {
+        let policy = SafetyPolicy::default();
+        let nums = [1, 2, 7, 8, 10];
+        assert_eq!(
+            highlight(&nums, &diagnose(&nums, &policy)),
+            "1 2 7 8 10\n  ^ ^"
+        );
+        let nums = [10, 3, 6, 7, 9];
+        assert_eq!(
+            highlight(&nums, &diagnose(&nums, &policy)),
+            "10 3 6 7 9\n--"
+        );
+        assert_eq!(highlight(&nums[1..], &ReportStatus::Safe), "3 6 7 9\n")
+    }
+
+    #[test]
+    fn test_parse_reports() {
+        assert_eq!(
+            parse_reports("7 6  4\t2 1\n\n   \n1 3 6 7 9\n"),
+            Ok(vec![vec![7, 6, 4, 2, 1], vec![1, 3, 6, 7, 9]])
+        );
+        assert_eq!(
+            parse_reports("7 6 4 2 1\n\n1 3 x 7 9"),
+            Err(ParseError {
+                line: 3,
+                message: "invalid level \"x\": invalid digit found in string".to_string()
+            })
+        )
+    }
+}
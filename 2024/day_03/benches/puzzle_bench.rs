@@ -0,0 +1,65 @@
+use criterion::{criterion_group, criterion_main, BenchmarkId, Criterion, Throughput};
+
+use day_03::{
+    parse_located, parse_located_many_till, process_part_one, process_part_two, InstructionSet,
+};
+
+fn bench_part_one(c: &mut Criterion) {
+    c.bench_function("day_03 part one", |b| {
+        b.iter(|| process_part_one(include_str!("../input.txt")))
+    });
+}
+
+fn bench_part_two(c: &mut Criterion) {
+    c.bench_function("day_03 part two", |b| {
+        b.iter(|| process_part_two(include_str!("../input.txt")))
+    });
+}
+
+/// About `size` bytes of corrupted memory, mostly noise with an instruction or
+/// near miss every so often.
+fn memory_dump(size: usize) -> String {
+    const FRAGMENTS: [&str; 8] = [
+        "mul(2,4)",
+        "mul[3,7]",
+        "don't()",
+        "mul(32,64]",
+        "do()",
+        "mul(11,8)",
+        "undo()?",
+        "what()",
+    ];
+    let mut dump = String::with_capacity(size + 16);
+    let mut seed: u32 = 1;
+    while dump.len() < size {
+        seed ^= seed << 13;
+        seed ^= seed >> 17;
+        seed ^= seed << 5;
+        if seed.is_multiple_of(8) {
+            dump += FRAGMENTS[(seed / 8 % 8) as usize];
+        } else {
+            dump.push(b"!@#$%^&*()+-_<>?,' xmdwhen"[(seed % 26) as usize] as char);
+        }
+    }
+    dump
+}
+
+fn bench_parsers(c: &mut Criterion) {
+    let mut group = c.benchmark_group("day_03 parser");
+    group.sample_size(10);
+    let set = InstructionSet::puzzle();
+    for size in [1 << 20, 8 << 20] {
+        let dump = memory_dump(size);
+        group.throughput(Throughput::Bytes(dump.len() as u64));
+        group.bench_with_input(BenchmarkId::new("many_till", size), &dump, |b, dump| {
+            b.iter(|| parse_located_many_till(dump, &set))
+        });
+        group.bench_with_input(BenchmarkId::new("scanner", size), &dump, |b, dump| {
+            b.iter(|| parse_located(dump, &set))
+        });
+    }
+    group.finish();
+}
+
+criterion_group!(benches, bench_part_one, bench_part_two, bench_parsers);
+criterion_main!(benches);
@@ -0,0 +1,32 @@
+use aoc_config::CONFIG_FILE;
+use day_03::{explain, highlight, lint, process_part_two_with_width, Config};
+use std::{env, fs};
+
+fn main() {
+    let input = fs::read_to_string("input.txt").unwrap();
+    let args: Vec<String> = env::args().collect();
+    let mut config: Config = aoc_config::load(CONFIG_FILE).expect("error loading the config file");
+    if let Some(i) = args.iter().position(|arg| arg == "--leniency") {
+        let leniency = args.get(i + 1).expect("missing value for --leniency");
+        config.leniency = leniency.parse().unwrap_or_else(|e| panic!("{}", e));
+    }
+    if let Some(i) = args.iter().position(|arg| arg == "--width") {
+        let width = args.get(i + 1).expect("missing value for --width");
+        config.width = width.parse().unwrap_or_else(|e| panic!("{}", e));
+    }
+    let set = config.instruction_set();
+    let answer = process_part_two_with_width(&input, &set, config.width);
+    println!("{}", answer.unwrap_or_else(|e| panic!("{}", e)));
+    if args.iter().any(|arg| arg == "--explain") {
+        println!("{}", explain(&input, &set))
+    }
+    if args.iter().any(|arg| arg == "--highlight") {
+        println!("{}", highlight(&input, &set))
+    }
+    if args.iter().any(|arg| arg == "--lint") {
+        for miss in lint(&input, &set) {
+            let text = &input[miss.span.clone()];
+            println!("{}: {} ({})", miss.span.start, text, miss.reason())
+        }
+    }
+}
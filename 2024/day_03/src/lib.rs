@@ -0,0 +1,1475 @@
+use aoc_config::Validate;
+use memchr::{memchr, memchr2, memchr3};
+use nom::{
+    branch::alt,
+    bytes::{complete, streaming},
+    character::{complete as character, complete::anychar, streaming as character_streaming},
+    combinator::{consumed, map, value},
+    error::{Error, ErrorKind},
+    multi::{many1, many_till},
+    sequence::{delimited, separated_pair},
+    IResult, Needed, Offset,
+};
+use serde::Deserialize;
+use std::{
+    fmt,
+    io::{self, Read},
+    ops::Range,
+    str::FromStr,
+};
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum Op {
+    Add,
+    Sub,
+    Mul,
+    Div,
+}
+
+impl Op {
+    pub const ALL: [Op; 4] = [Op::Add, Op::Sub, Op::Mul, Op::Div];
+
+    pub fn name(&self) -> &'static str {
+        match self {
+            Op::Add => "add",
+            Op::Sub => "sub",
+            Op::Mul => "mul",
+            Op::Div => "div",
+        }
+    }
+
+    fn apply<A: Accumulator>(&self, a: A, b: A) -> Result<A, EvalError> {
+        match self {
+            Op::Add => a.checked_add(b),
+            Op::Sub => a.checked_sub(b),
+            Op::Mul => a.checked_mul(b),
+            Op::Div if b == A::default() => return Err(EvalError::DivisionByZero),
+            Op::Div => a.checked_div(b),
+        }
+        .ok_or(EvalError::Overflow)
+    }
+}
+
+/// An integer type to evaluate calls and add them up in. Every operation is
+/// checked, so a result that doesn't fit, including a negative one for an
+/// unsigned type, is an `EvalError::Overflow` rather than wrapping around. So is
+/// a number in the memory that's too big for it.
+pub trait Accumulator:
+    Copy + Default + PartialEq + fmt::Debug + fmt::Display + TryFrom<u128>
+{
+    fn checked_add(self, other: Self) -> Option<Self>;
+    fn checked_sub(self, other: Self) -> Option<Self>;
+    fn checked_mul(self, other: Self) -> Option<Self>;
+    fn checked_div(self, other: Self) -> Option<Self>;
+}
+
+macro_rules! impl_accumulator {
+    ($($t:ty),*) => {$(
+        impl Accumulator for $t {
+            fn checked_add(self, other: Self) -> Option<Self> {
+                <$t>::checked_add(self, other)
+            }
+
+            fn checked_sub(self, other: Self) -> Option<Self> {
+                <$t>::checked_sub(self, other)
+            }
+
+            fn checked_mul(self, other: Self) -> Option<Self> {
+                <$t>::checked_mul(self, other)
+            }
+
+            fn checked_div(self, other: Self) -> Option<Self> {
+                <$t>::checked_div(self, other)
+            }
+        }
+    )*};
+}
+
+impl_accumulator!(u32, u64, u128, i64, i128);
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum EvalError {
+    DivisionByZero,
+    Overflow,
+}
+
+impl fmt::Display for EvalError {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match self {
+            EvalError::DivisionByZero => write!(f, "division by zero"),
+            EvalError::Overflow => write!(f, "arithmetic overflow"),
+        }
+    }
+}
+
+impl std::error::Error for EvalError {}
+
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub enum Expr {
+    Num(u128),
+    /// A number too big even for a `u128`, kept as written. It always evaluates
+    /// to `EvalError::Overflow`.
+    TooBig(String),
+    Call(Op, Box<Expr>, Box<Expr>),
+}
+
+impl Expr {
+    pub fn eval<A: Accumulator>(&self) -> Result<A, EvalError> {
+        match self {
+            Expr::Num(n) => A::try_from(*n).map_err(|_| EvalError::Overflow),
+            Expr::TooBig(_) => Err(EvalError::Overflow),
+            Expr::Call(op, a, b) => op.apply(a.eval()?, b.eval()?),
+        }
+    }
+}
+
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub enum Command {
+    /// A top level call, always an `Expr::Call`.
+    Call(Expr),
+    Do,
+    Dont,
+}
+
+impl Command {
+    pub fn mul(a: u32, b: u32) -> Self {
+        Command::Call(Expr::Call(
+            Op::Mul,
+            Box::new(Expr::Num(a.into())),
+            Box::new(Expr::Num(b.into())),
+        ))
+    }
+}
+
+/// Which almost-instructions count as instructions. Each level also accepts
+/// everything the levels before it do.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, PartialOrd, Ord, Deserialize)]
+#[serde(rename_all = "kebab-case")]
+pub enum Leniency {
+    /// Exactly as in the puzzle: `mul(X,Y)` with 1-3 digit numbers.
+    Strict,
+    /// Numbers with more than 3 digits, e.g. `mul(1234,5)`.
+    LongNumbers,
+    /// Spaces and tabs around the brackets and arguments, e.g. `mul ( 2 , 4 )`.
+    Whitespace,
+    /// Square or curly brackets instead of parentheses, even mismatched ones,
+    /// e.g. `mul[3,7]` or `mul(32,64]`.
+    Brackets,
+}
+
+impl Leniency {
+    pub const ALL: [Leniency; 4] = [
+        Leniency::Strict,
+        Leniency::LongNumbers,
+        Leniency::Whitespace,
+        Leniency::Brackets,
+    ];
+
+    pub fn name(&self) -> &'static str {
+        match self {
+            Leniency::Strict => "strict",
+            Leniency::LongNumbers => "long-numbers",
+            Leniency::Whitespace => "whitespace",
+            Leniency::Brackets => "brackets",
+        }
+    }
+}
+
+impl FromStr for Leniency {
+    type Err = String;
+
+    fn from_str(s: &str) -> Result<Self, Self::Err> {
+        Leniency::ALL
+            .into_iter()
+            .find(|leniency| leniency.name() == s)
+            .ok_or_else(|| format!("unknown leniency {}", s))
+    }
+}
+
+/// The instructions to look for in the corrupted memory. `do()` and `don't()` are
+/// always recognised.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct InstructionSet {
+    pub ops: Vec<Op>,
+    /// How deeply calls can be nested in each other's arguments. 0 means every
+    /// argument has to be a number.
+    pub max_depth: usize,
+    pub leniency: Leniency,
+}
+
+impl InstructionSet {
+    /// Only `mul` with numeric arguments, as in the puzzle.
+    pub fn puzzle() -> Self {
+        InstructionSet {
+            ops: vec![Op::Mul],
+            max_depth: 0,
+            leniency: Leniency::Strict,
+        }
+    }
+
+    /// Every `Op`, nested up to 16 calls deep.
+    pub fn extended() -> Self {
+        InstructionSet {
+            ops: Op::ALL.to_vec(),
+            max_depth: 16,
+            leniency: Leniency::Strict,
+        }
+    }
+}
+
+impl Default for InstructionSet {
+    fn default() -> Self {
+        InstructionSet::puzzle()
+    }
+}
+
+/// The integer type answers are added up in.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Deserialize)]
+#[serde(rename_all = "lowercase")]
+pub enum Width {
+    U32,
+    U64,
+    U128,
+}
+
+impl Width {
+    pub const ALL: [Width; 3] = [Width::U32, Width::U64, Width::U128];
+
+    pub fn name(&self) -> &'static str {
+        match self {
+            Width::U32 => "u32",
+            Width::U64 => "u64",
+            Width::U128 => "u128",
+        }
+    }
+}
+
+impl FromStr for Width {
+    type Err = String;
+
+    fn from_str(s: &str) -> Result<Self, Self::Err> {
+        Width::ALL
+            .into_iter()
+            .find(|width| width.name() == s)
+            .ok_or_else(|| format!("unknown width {}", s))
+    }
+}
+
+#[derive(Debug, Deserialize)]
+#[serde(default, deny_unknown_fields)]
+pub struct Config {
+    /// Which almost-instructions to count anyway.
+    pub leniency: Leniency,
+    /// What to add the answers up in.
+    pub width: Width,
+}
+
+impl Default for Config {
+    fn default() -> Self {
+        Config {
+            leniency: Leniency::Strict,
+            width: Width::U64,
+        }
+    }
+}
+
+impl Validate for Config {
+    fn validate(&self) -> Result<(), String> {
+        Ok(())
+    }
+}
+
+impl Config {
+    /// The puzzle's instructions at the configured leniency.
+    pub fn instruction_set(&self) -> InstructionSet {
+        InstructionSet {
+            leniency: self.leniency,
+            ..InstructionSet::puzzle()
+        }
+    }
+}
+
+// The parsers take `STREAMING` to pick between nom's complete parsers, for when
+// the input is all there is, and its streaming ones, which fail with `Incomplete`
+// when the input ends before they can tell whether they match.
+
+fn parse_tag<'a, const STREAMING: bool>(
+    input: &'a str,
+    t: &'static str,
+) -> IResult<&'a str, &'a str> {
+    if STREAMING {
+        streaming::tag(t)(input)
+    } else {
+        complete::tag(t)(input)
+    }
+}
+
+fn parse_char<const STREAMING: bool>(input: &str, c: char) -> IResult<&str, char> {
+    if STREAMING {
+        character_streaming::char(c)(input)
+    } else {
+        character::char(c)(input)
+    }
+}
+
+fn parse_op<'a, const STREAMING: bool>(
+    input: &'a str,
+    set: &InstructionSet,
+) -> IResult<&'a str, Op> {
+    if let Some(found) = set
+        .ops
+        .iter()
+        .find_map(|&op| Some((input.strip_prefix(op.name())?, op)))
+    {
+        return Ok(found);
+    }
+    if STREAMING && set.ops.iter().any(|op| op.name().starts_with(input)) {
+        return Err(nom::Err::Incomplete(Needed::Unknown));
+    }
+    Err(nom::Err::Error(Error::new(input, ErrorKind::Tag)))
+}
+
+fn parse_num<'a, const STREAMING: bool>(
+    input: &'a str,
+    set: &InstructionSet,
+) -> IResult<&'a str, Expr> {
+    let max_digits = if set.leniency >= Leniency::LongNumbers {
+        usize::MAX
+    } else {
+        3
+    };
+    let is_digit = |c: char| c.is_ascii_digit();
+    let digits = move |input: &'a str| {
+        if STREAMING {
+            streaming::take_while_m_n(1, max_digits, is_digit)(input)
+        } else {
+            complete::take_while_m_n(1, max_digits, is_digit)(input)
+        }
+    };
+    // Only overflow can make the parse fail, and that's the accumulator's to
+    // report rather than a reason to skip the call
+    map(digits, |digits: &str| match digits.parse() {
+        Ok(n) => Expr::Num(n),
+        Err(_) => Expr::TooBig(digits.to_string()),
+    })(input)
+}
+
+fn parse_arg<'a, const STREAMING: bool>(
+    input: &'a str,
+    set: &InstructionSet,
+    depth: usize,
+) -> IResult<&'a str, Expr> {
+    let (input, _) = parse_space::<STREAMING>(input, set)?;
+    let (input, arg) = if depth < set.max_depth {
+        alt((
+            |input| parse_num::<STREAMING>(input, set),
+            |input| parse_call::<STREAMING>(input, set, depth + 1),
+        ))(input)?
+    } else {
+        parse_num::<STREAMING>(input, set)?
+    };
+    let (input, _) = parse_space::<STREAMING>(input, set)?;
+    Ok((input, arg))
+}
+
+fn parse_space<'a, const STREAMING: bool>(
+    input: &'a str,
+    set: &InstructionSet,
+) -> IResult<&'a str, &'a str> {
+    if set.leniency < Leniency::Whitespace {
+        Ok((input, ""))
+    } else if STREAMING {
+        character_streaming::space0(input)
+    } else {
+        character::space0(input)
+    }
+}
+
+fn parse_bracket<'a, const STREAMING: bool>(
+    input: &'a str,
+    set: &InstructionSet,
+    bracket: char,
+    lenient: &'static str,
+) -> IResult<&'a str, char> {
+    if set.leniency < Leniency::Brackets {
+        parse_char::<STREAMING>(input, bracket)
+    } else if STREAMING {
+        character_streaming::one_of(lenient)(input)
+    } else {
+        character::one_of(lenient)(input)
+    }
+}
+
+fn parse_call<'a, const STREAMING: bool>(
+    input: &'a str,
+    set: &InstructionSet,
+    depth: usize,
+) -> IResult<&'a str, Expr> {
+    let (input, op) = parse_op::<STREAMING>(input, set)?;
+    let (input, _) = parse_space::<STREAMING>(input, set)?;
+    map(
+        delimited(
+            |input| parse_bracket::<STREAMING>(input, set, '(', "([{"),
+            separated_pair(
+                |input| parse_arg::<STREAMING>(input, set, depth),
+                |input| parse_char::<STREAMING>(input, ','),
+                |input| parse_arg::<STREAMING>(input, set, depth),
+            ),
+            |input| parse_bracket::<STREAMING>(input, set, ')', ")]}"),
+        ),
+        move |(a, b)| Expr::Call(op, Box::new(a), Box::new(b)),
+    )(input)
+}
+
+/// A command and the bytes of the corrupted memory it was parsed from.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct Located {
+    pub command: Command,
+    pub span: Range<usize>,
+}
+
+fn parse_instruction<'a, const STREAMING: bool>(
+    input: &'a str,
+    set: &InstructionSet,
+) -> IResult<&'a str, Command> {
+    let parse_do = value(Command::Do, |input| parse_tag::<STREAMING>(input, "do()"));
+    let parse_dont = value(Command::Dont, |input| {
+        parse_tag::<STREAMING>(input, "don't()")
+    });
+    let parse_command = map(
+        |input| parse_call::<STREAMING>(input, set, 0),
+        Command::Call,
+    );
+    alt((parse_do, parse_dont, parse_command))(input)
+}
+
+/// The original parser, which retries at every character and fails if there are
+/// no instructions at all. Kept to benchmark `parse_located` against.
+#[doc(hidden)]
+pub fn parse_located_many_till(input: &str, set: &InstructionSet) -> Vec<Located> {
+    let memory = input;
+    let (_, cmds) = many1(map(
+        many_till(
+            anychar,
+            consumed(|input| parse_instruction::<false>(input, set)),
+        ),
+        |(_, (text, command))| {
+            let start = memory.offset(text);
+            Located {
+                command,
+                span: start..start + text.len(),
+            }
+        },
+    ))(input)
+    .unwrap();
+    cmds
+}
+
+/// Position of the first byte in `haystack` that's one of `needles`.
+fn find_candidate(haystack: &[u8], needles: &[u8]) -> Option<usize> {
+    match *needles {
+        [a] => memchr(a, haystack),
+        [a, b] => memchr2(a, b, haystack),
+        [a, b, c] => memchr3(a, b, c, haystack),
+        _ => haystack.iter().position(|b| needles.contains(b)),
+    }
+}
+
+/// The bytes an instruction from `set` can start with. Only these positions are
+/// worth trying to parse at.
+fn start_bytes(set: &InstructionSet) -> Vec<u8> {
+    let mut needles: Vec<u8> = set.ops.iter().map(|op| op.name().as_bytes()[0]).collect();
+    needles.push(b'd');
+    needles.sort_unstable();
+    needles.dedup();
+    needles
+}
+
+/// Every instruction from `set` found in the corrupted memory, in order, with
+/// where it was found.
+pub fn parse_located(input: &str, set: &InstructionSet) -> Vec<Located> {
+    let needles = start_bytes(set);
+
+    let mut cmds = Vec::new();
+    let mut pos = 0;
+    while let Some(i) = find_candidate(&input.as_bytes()[pos..], &needles) {
+        let start = pos + i;
+        match parse_instruction::<false>(&input[start..], set) {
+            Ok((rest, command)) => {
+                pos = input.len() - rest.len();
+                cmds.push(Located {
+                    command,
+                    span: start..pos,
+                });
+            }
+            Err(_) => pos = start + 1,
+        }
+    }
+    cmds
+}
+
+/// Every instruction from `set` found in the corrupted memory, in order.
+pub fn parse_commands(input: &str, set: &InstructionSet) -> Vec<Command> {
+    parse_located(input, set)
+        .into_iter()
+        .map(|located| located.command)
+        .collect()
+}
+
+/// Runs commands, adding up the value of every call. With `conditionals`, calls
+/// after a `don't()` are skipped until the next `do()`.
+#[derive(Debug)]
+pub struct Interpreter<A> {
+    conditionals: bool,
+    enabled: bool,
+    total: A,
+}
+
+impl<A: Accumulator> Interpreter<A> {
+    pub fn new(conditionals: bool) -> Self {
+        Interpreter {
+            conditionals,
+            enabled: true,
+            total: A::default(),
+        }
+    }
+
+    pub fn enabled(&self) -> bool {
+        self.enabled
+    }
+
+    pub fn total(&self) -> A {
+        self.total
+    }
+
+    /// Returns the value of the command if it was a call that got counted.
+    pub fn execute(&mut self, cmd: &Command) -> Result<Option<A>, EvalError> {
+        match cmd {
+            Command::Do => self.enabled = true,
+            Command::Dont if self.conditionals => self.enabled = false,
+            Command::Dont => (),
+            Command::Call(expr) if self.enabled => {
+                let value = expr.eval()?;
+                self.total = self.total.checked_add(value).ok_or(EvalError::Overflow)?;
+                return Ok(Some(value));
+            }
+            Command::Call(_) => (),
+        }
+        Ok(None)
+    }
+
+    pub fn run<'a>(&mut self, cmds: impl IntoIterator<Item = &'a Command>) -> Result<A, EvalError> {
+        for cmd in cmds {
+            self.execute(cmd)?;
+        }
+        Ok(self.total)
+    }
+}
+
+pub fn process_part_one(input: &str) -> u64 {
+    process_part_one_with_set(input, &InstructionSet::puzzle()).unwrap()
+}
+
+/// Part one with the instructions in `set`, adding up in `A`.
+pub fn process_part_one_with_set<A: Accumulator>(
+    input: &str,
+    set: &InstructionSet,
+) -> Result<A, EvalError> {
+    let cmds = parse_commands(input, set);
+    Interpreter::new(false).run(&cmds)
+}
+
+/// Part one with the instructions in `set`, adding up in the integer type
+/// `width` picks. The answer is a string since its type depends on `width`.
+pub fn process_part_one_with_width(
+    input: &str,
+    set: &InstructionSet,
+    width: Width,
+) -> Result<String, EvalError> {
+    match width {
+        Width::U32 => process_part_one_with_set::<u32>(input, set).map(|n| n.to_string()),
+        Width::U64 => process_part_one_with_set::<u64>(input, set).map(|n| n.to_string()),
+        Width::U128 => process_part_one_with_set::<u128>(input, set).map(|n| n.to_string()),
+    }
+}
+
+pub fn process_part_two(input: &str) -> u64 {
+    process_part_two_with_set(input, &InstructionSet::puzzle()).unwrap()
+}
+
+/// Part two with the instructions in `set`, adding up in `A`.
+pub fn process_part_two_with_set<A: Accumulator>(
+    input: &str,
+    set: &InstructionSet,
+) -> Result<A, EvalError> {
+    let cmds = parse_commands(input, set);
+    Interpreter::new(true).run(&cmds)
+}
+
+/// Part two with the instructions in `set`, adding up in the integer type
+/// `width` picks. The answer is a string since its type depends on `width`.
+pub fn process_part_two_with_width(
+    input: &str,
+    set: &InstructionSet,
+    width: Width,
+) -> Result<String, EvalError> {
+    match width {
+        Width::U32 => process_part_two_with_set::<u32>(input, set).map(|n| n.to_string()),
+        Width::U64 => process_part_two_with_set::<u64>(input, set).map(|n| n.to_string()),
+        Width::U128 => process_part_two_with_set::<u128>(input, set).map(|n| n.to_string()),
+    }
+}
+
+impl fmt::Display for Expr {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match self {
+            Expr::Num(n) => write!(f, "{}", n),
+            Expr::TooBig(digits) => write!(f, "{}", digits),
+            Expr::Call(op, a, b) => write!(f, "{}({},{})", op.name(), a, b),
+        }
+    }
+}
+
+impl fmt::Display for Command {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match self {
+            Command::Call(expr) => write!(f, "{}", expr),
+            Command::Do => write!(f, "do()"),
+            Command::Dont => write!(f, "don't()"),
+        }
+    }
+}
+
+/// Every instruction found in the corrupted memory, with whether `mul`s were
+/// enabled at that point. Printed by the binaries with `--explain`.
+#[derive(Debug)]
+pub struct Explanation {
+    pub commands: Vec<(Command, bool)>,
+}
+
+impl fmt::Display for Explanation {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        let mut total: u128 = 0;
+        let mut enabled_total: u128 = 0;
+        for (cmd, active) in &self.commands {
+            match cmd {
+                Command::Call(expr) => match expr.eval::<u128>() {
+                    Ok(value) => {
+                        total = total.saturating_add(value);
+                        if *active {
+                            enabled_total = enabled_total.saturating_add(value);
+                            writeln!(f, "{} = {}", cmd, value)?
+                        } else {
+                            writeln!(f, "{} = {} (disabled)", cmd, value)?
+                        }
+                    }
+                    Err(e) => writeln!(f, "{} = {}", cmd, e)?,
+                },
+                _ => writeln!(f, "{}", cmd)?,
+            }
+        }
+        writeln!(f, "total: {}", total)?;
+        write!(f, "enabled total: {}", enabled_total)
+    }
+}
+
+pub fn explain(input: &str, set: &InstructionSet) -> Explanation {
+    let cmds = parse_commands(input, set);
+    let mut interpreter = Interpreter::<u64>::new(true);
+    let commands = cmds
+        .into_iter()
+        .map(|cmd| {
+            // Only the enabled state matters here, not the running total
+            let _ = interpreter.execute(&cmd);
+            (cmd, interpreter.enabled())
+        })
+        .collect();
+
+    Explanation { commands }
+}
+
+/// How far past its start an instruction is looked for in a stream before giving
+/// up on it. Only lenient instruction sets, where spaces and numbers can go on
+/// indefinitely, allow instructions this long, and only in a stream are they
+/// skipped rather than found.
+pub const MAX_STREAMED_INSTRUCTION: usize = 64 * 1024;
+
+/// Bytes looked at past a candidate before checking whether the instruction needs
+/// more. Longer than any instruction from the puzzle's set.
+const LOOKAHEAD: usize = 64;
+
+/// Extracts instructions from a reader a chunk at a time, so the corrupted memory
+/// never has to fit in memory. Spans are byte offsets from the start of the
+/// stream. At most `MAX_STREAMED_INSTRUCTION` bytes are held on to past the last
+/// chunk boundary. Returned by `stream_commands`.
+pub struct CommandStream<R> {
+    reader: R,
+    set: InstructionSet,
+    needles: Vec<u8>,
+    /// Bytes that can appear in an instruction. An instruction can't continue past
+    /// any other byte, so a parse that runs out of input at one has failed.
+    alphabet: [bool; 256],
+    chunk_size: usize,
+    buf: Vec<u8>,
+    /// Position of `buf[0]` in the stream.
+    offset: usize,
+    pos: usize,
+    eof: bool,
+}
+
+/// Instructions from `set` found in `reader`, read 64 KiB at a time.
+pub fn stream_commands<R: Read>(reader: R, set: InstructionSet) -> CommandStream<R> {
+    let mut alphabet = [false; 256];
+    let bytes = set.ops.iter().flat_map(|op| op.name().bytes());
+    for b in bytes.chain(b"don't()0123456789,".iter().copied()) {
+        alphabet[b as usize] = true;
+    }
+    if set.leniency >= Leniency::Whitespace {
+        alphabet[b' ' as usize] = true;
+        alphabet[b'\t' as usize] = true;
+    }
+    if set.leniency >= Leniency::Brackets {
+        for &b in b"[]{}" {
+            alphabet[b as usize] = true;
+        }
+    }
+    CommandStream {
+        reader,
+        needles: start_bytes(&set),
+        set,
+        alphabet,
+        chunk_size: 64 * 1024,
+        buf: Vec::new(),
+        offset: 0,
+        pos: 0,
+        eof: false,
+    }
+}
+
+impl<R: Read> CommandStream<R> {
+    pub fn with_chunk_size(mut self, chunk_size: usize) -> Self {
+        assert!(chunk_size > 0, "chunk size must be positive");
+        self.chunk_size = chunk_size;
+        self
+    }
+
+    /// Drops everything before `pos` and reads the next chunk.
+    fn refill(&mut self) -> io::Result<()> {
+        self.buf.drain(..self.pos);
+        self.offset += self.pos;
+        self.pos = 0;
+
+        let len = self.buf.len();
+        self.buf.resize(len + self.chunk_size, 0);
+        let read = loop {
+            match self.reader.read(&mut self.buf[len..]) {
+                Err(e) if e.kind() == io::ErrorKind::Interrupted => continue,
+                result => break result,
+            }
+        };
+        match read {
+            Ok(n) => {
+                self.buf.truncate(len + n);
+                self.eof = n == 0;
+                Ok(())
+            }
+            Err(e) => {
+                self.buf.truncate(len);
+                Err(e)
+            }
+        }
+    }
+}
+
+impl<R: Read> Iterator for CommandStream<R> {
+    type Item = io::Result<Located>;
+
+    fn next(&mut self) -> Option<Self::Item> {
+        loop {
+            let Some(i) = find_candidate(&self.buf[self.pos..], &self.needles) else {
+                self.pos = self.buf.len();
+                if self.eof {
+                    return None;
+                }
+                if let Err(e) = self.refill() {
+                    return Some(Err(e));
+                }
+                continue;
+            };
+
+            let start = self.pos + i;
+            match self.parse_at(start) {
+                Step::Found(command, end) => {
+                    self.pos = end;
+                    let span = self.offset + start..self.offset + end;
+                    return Some(Ok(Located { command, span }));
+                }
+                Step::Refill => {
+                    // The instruction might carry on in the next chunk
+                    self.pos = start;
+                    if let Err(e) = self.refill() {
+                        return Some(Err(e));
+                    }
+                }
+                Step::Skip => self.pos = start + 1,
+            }
+        }
+    }
+}
+
+/// What to do about a candidate in `CommandStream`.
+enum Step {
+    /// An instruction, ending at the given position in the buffer.
+    Found(Command, usize),
+    /// The buffer ends partway through what might be an instruction.
+    Refill,
+    Skip,
+}
+
+impl<R> CommandStream<R> {
+    /// Parses at `start` looking at as little of the buffer as it can, so that
+    /// each candidate costs about the length of the instruction rather than the
+    /// length of the run of instruction-like bytes it's in.
+    fn parse_at(&self, start: usize) -> Step {
+        let mut window = LOOKAHEAD;
+        loop {
+            let limit = self.buf.len().min(start + window);
+            let end = self.buf[start..limit]
+                .iter()
+                .position(|&b| !self.alphabet[b as usize])
+                .map_or(limit, |n| start + n);
+            // Everything in the alphabet is ASCII
+            let text = std::str::from_utf8(&self.buf[start..end]).unwrap();
+            match parse_instruction::<true>(text, &self.set) {
+                // Every instruction ends in `)`, so more input can't change it
+                Ok((rest, command)) => return Step::Found(command, end - rest.len()),
+                Err(nom::Err::Incomplete(_)) if window >= MAX_STREAMED_INSTRUCTION => {
+                    return Step::Skip
+                }
+                // Cut short by the window, look further
+                Err(nom::Err::Incomplete(_)) if end < self.buf.len() && end == limit => window *= 2,
+                Err(nom::Err::Incomplete(_)) if end == self.buf.len() && !self.eof => {
+                    return Step::Refill
+                }
+                // Followed by a byte that can't be part of an instruction
+                Err(_) => return Step::Skip,
+            }
+        }
+    }
+}
+
+/// One way a near miss differs from a strict instruction.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum Relaxation {
+    LongNumber,
+    Whitespace,
+    Bracket,
+}
+
+impl Relaxation {
+    /// The least lenient level that allows it.
+    pub fn leniency(&self) -> Leniency {
+        match self {
+            Relaxation::LongNumber => Leniency::LongNumbers,
+            Relaxation::Whitespace => Leniency::Whitespace,
+            Relaxation::Bracket => Leniency::Brackets,
+        }
+    }
+
+    pub fn description(&self) -> &'static str {
+        match self {
+            Relaxation::LongNumber => "number with more than 3 digits",
+            Relaxation::Whitespace => "whitespace inside the call",
+            Relaxation::Bracket => "brackets other than parentheses",
+        }
+    }
+
+    /// Every relaxation that `text`, a call accepted at some leniency, relies on.
+    fn found_in(text: &str) -> Vec<Relaxation> {
+        let long_number = text
+            .split(|c: char| !c.is_ascii_digit())
+            .any(|digits| digits.len() > 3);
+        [
+            (Relaxation::LongNumber, long_number),
+            (Relaxation::Whitespace, text.contains([' ', '\t'])),
+            (Relaxation::Bracket, text.contains(['[', ']', '{', '}'])),
+        ]
+        .into_iter()
+        .filter_map(|(relaxation, found)| found.then_some(relaxation))
+        .collect()
+    }
+}
+
+/// Something that looks like a call but isn't one at the instruction set's
+/// leniency.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct NearMiss {
+    pub span: Range<usize>,
+    /// The least lenient level that would accept it.
+    pub needs: Leniency,
+    /// Every way it goes beyond the instruction set's leniency, in the order of
+    /// the levels that allow them.
+    pub relaxations: Vec<Relaxation>,
+}
+
+impl NearMiss {
+    /// Every relaxation the call needs, e.g. "number with more than 3 digits,
+    /// whitespace inside the call" for `mul( 1234 ,5)`.
+    pub fn reason(&self) -> String {
+        self.relaxations
+            .iter()
+            .map(Relaxation::description)
+            .collect::<Vec<_>>()
+            .join(", ")
+    }
+}
+
+/// Every near miss in the corrupted memory, in order: calls that `set` rejects
+/// but a more lenient instruction set would accept.
+pub fn lint(input: &str, set: &InstructionSet) -> Vec<NearMiss> {
+    let needles = start_bytes(set);
+    let at = |leniency| InstructionSet {
+        leniency,
+        ..set.clone()
+    };
+    let levels: Vec<(Leniency, InstructionSet)> = Leniency::ALL
+        .into_iter()
+        .filter(|&leniency| leniency > set.leniency)
+        .map(|leniency| (leniency, at(leniency)))
+        .collect();
+
+    let mut misses = Vec::new();
+    let mut pos = 0;
+    while let Some(i) = find_candidate(&input.as_bytes()[pos..], &needles) {
+        let start = pos + i;
+        if let Ok((rest, _)) = parse_instruction::<false>(&input[start..], set) {
+            pos = input.len() - rest.len();
+            continue;
+        }
+        let accepted = levels.iter().find_map(|(leniency, lenient)| {
+            let (rest, _) = parse_call::<false>(&input[start..], lenient, 0).ok()?;
+            Some((*leniency, input.len() - rest.len()))
+        });
+        if let Some((needs, end)) = accepted {
+            let relaxations = Relaxation::found_in(&input[start..end])
+                .into_iter()
+                .filter(|relaxation| relaxation.leniency() > set.leniency)
+                .collect();
+            misses.push(NearMiss {
+                span: start..end,
+                needs,
+                relaxations,
+            });
+        }
+        pos = start + 1;
+    }
+    misses
+}
+
+const ENABLED: &str = "\x1b[1;32m";
+const DISABLED: &str = "\x1b[2;31m";
+const DO: &str = "\x1b[36m";
+const DONT: &str = "\x1b[33m";
+const RESET: &str = "\x1b[0m";
+
+/// The corrupted memory with ANSI colours on the recognised instructions: calls
+/// that count in bold green, calls disabled by `don't()` in dim red, `do()` in
+/// cyan and `don't()` in yellow. Everything else is left as it was.
+pub fn highlight(input: &str, set: &InstructionSet) -> String {
+    let mut interpreter = Interpreter::<u64>::new(true);
+    let mut out = String::with_capacity(input.len());
+    let mut end = 0;
+    for Located { command, span } in parse_located(input, set) {
+        let _ = interpreter.execute(&command);
+        let colour = match command {
+            Command::Do => DO,
+            Command::Dont => DONT,
+            Command::Call(_) if interpreter.enabled() => ENABLED,
+            Command::Call(_) => DISABLED,
+        };
+        out += &input[end..span.start];
+        out += colour;
+        out += &input[span.clone()];
+        out += RESET;
+        end = span.end;
+    }
+    out += &input[end..];
+    out
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    const TEST_INPUT: &str =
+        "xmul(2,4)%&mul[3,7]!@^do_not_mul(5,5)+mul(32,64]then(mul(11,8)mul(8,5))";
+    const TEST_INPUT_2: &str =
+        "xmul(2,4)&mul[3,7]!^don't()_mul(5,5)+mul(32,64](mul(11,8)undo()?mul(8,5))";
+
+    #[test]
+    fn test_part_one() {
+        assert_eq!(process_part_one(TEST_INPUT), 161)
+    }
+
+    #[test]
+    fn test_part_two() {
+        assert_eq!(process_part_two(TEST_INPUT_2), 48)
+    }
+
+    #[test]
+    fn test_explain() {
+        let explanation = explain(TEST_INPUT_2, &InstructionSet::puzzle());
+        assert_eq!(
+            explanation.commands,
+            vec![
+                (Command::mul(2, 4), true),
+                (Command::Dont, false),
+                (Command::mul(5, 5), false),
+                (Command::mul(11, 8), false),
+                (Command::Do, true),
+                (Command::mul(8, 5), true),
+            ]
+        );
+        assert!(explanation.to_string().ends_with("enabled total: 48"));
+
+        // Explains the same instructions the answer was computed from
+        let mut set = InstructionSet::puzzle();
+        set.leniency = Leniency::Whitespace;
+        assert_eq!(
+            explain("mul( 2, 4)", &InstructionSet::puzzle()).commands,
+            vec![]
+        );
+        assert_eq!(
+            explain("mul( 2, 4)", &set).commands,
+            vec![(Command::mul(2, 4), true)]
+        )
+    }
+
+    #[test]
+    fn test_extended_instructions() {
+        let input = "mul(add(1,2),3)xsub(10,4)?div(9,don't()mul(2,2)do()div(sub(9,1),2)add(1,2]";
+        let cmds = parse_commands(input, &InstructionSet::extended());
+        let text: Vec<String> = cmds.iter().map(|cmd| cmd.to_string()).collect();
+        assert_eq!(
+            text,
+            vec![
+                "mul(add(1,2),3)",
+                "sub(10,4)",
+                "don't()",
+                "mul(2,2)",
+                "do()",
+                "div(sub(9,1),2)"
+            ]
+        );
+        assert_eq!(Interpreter::new(false).run(&cmds), Ok(23u64));
+        assert_eq!(Interpreter::new(true).run(&cmds), Ok(19u64));
+
+        // The puzzle's instruction set skips anything but plain muls
+        assert_eq!(
+            parse_commands(input, &InstructionSet::puzzle()),
+            vec![Command::Dont, Command::mul(2, 2), Command::Do]
+        );
+
+        let nested = InstructionSet {
+            max_depth: 1,
+            ..InstructionSet::extended()
+        };
+        // Too deep to parse as a whole, but the inner call is still found
+        assert_eq!(
+            parse_commands("add(1,mul(2,sub(4,3)))add(1,mul(2,3))", &nested),
+            vec![
+                Command::Call(Expr::Call(
+                    Op::Mul,
+                    Box::new(Expr::Num(2)),
+                    Box::new(Expr::Call(
+                        Op::Sub,
+                        Box::new(Expr::Num(4)),
+                        Box::new(Expr::Num(3))
+                    ))
+                )),
+                Command::Call(Expr::Call(
+                    Op::Add,
+                    Box::new(Expr::Num(1)),
+                    Box::new(Expr::Call(
+                        Op::Mul,
+                        Box::new(Expr::Num(2)),
+                        Box::new(Expr::Num(3))
+                    ))
+                ))
+            ]
+        )
+    }
+
+    #[test]
+    fn test_eval_errors() {
+        let cmds = parse_commands("div(1,sub(2,2))", &InstructionSet::extended());
+        assert_eq!(
+            Interpreter::<u64>::new(false).run(&cmds),
+            Err(EvalError::DivisionByZero)
+        );
+        let cmds = parse_commands(
+            "mul(mul(mul(mul(4000000000,4000000000),1),1),4000000000)",
+            &InstructionSet {
+                leniency: Leniency::LongNumbers,
+                ..InstructionSet::extended()
+            },
+        );
+        assert_eq!(
+            Interpreter::<i64>::new(false).run(&cmds),
+            Err(EvalError::Overflow)
+        )
+    }
+
+    #[test]
+    fn test_spans() {
+        let located = parse_located(TEST_INPUT_2, &InstructionSet::puzzle());
+        let text: Vec<&str> = located
+            .iter()
+            .map(|located| &TEST_INPUT_2[located.span.clone()])
+            .collect();
+        assert_eq!(
+            text,
+            vec![
+                "mul(2,4)",
+                "don't()",
+                "mul(5,5)",
+                "mul(11,8)",
+                "do()",
+                "mul(8,5)"
+            ]
+        );
+        assert_eq!(located[0].span, 1..9)
+    }
+
+    #[test]
+    fn test_highlight() {
+        let highlighted = highlight("xmul(2,4)don't()mul(5,5)?", &InstructionSet::puzzle());
+        assert_eq!(
+            highlighted,
+            "x\x1b[1;32mmul(2,4)\x1b[0m\x1b[33mdon't()\x1b[0m\x1b[2;31mmul(5,5)\x1b[0m?"
+        )
+    }
+
+    #[test]
+    fn test_scanner() {
+        for input in [TEST_INPUT, TEST_INPUT_2] {
+            for set in [InstructionSet::puzzle(), InstructionSet::extended()] {
+                assert_eq!(
+                    parse_located(input, &set),
+                    parse_located_many_till(input, &set)
+                );
+            }
+        }
+        let input = "mul(add(1,2),3)xsub(10,4)?div(9,don't()mul(2,2)do()div(sub(9,1),2)";
+        assert_eq!(
+            parse_located(input, &InstructionSet::extended()),
+            parse_located_many_till(input, &InstructionSet::extended())
+        );
+
+        assert_eq!(parse_located("", &InstructionSet::puzzle()), vec![]);
+        assert_eq!(
+            parse_located("no instructions", &InstructionSet::puzzle()),
+            vec![]
+        );
+        assert_eq!(process_part_one("mul(2,4)mul(3,"), 8);
+        assert_eq!(process_part_two("mul(2,4)don't()mul(3,3)do"), 8)
+    }
+
+    #[test]
+    fn test_stream_commands() {
+        for set in [InstructionSet::puzzle(), InstructionSet::extended()] {
+            let input = "mul(add(1,2),3)xsub(10,4)?div(9,don't()mul(2,2)do()div(sub(9,1),2)";
+            for input in [TEST_INPUT, TEST_INPUT_2, input] {
+                for chunk_size in 1..=9 {
+                    let streamed: Vec<Located> = stream_commands(input.as_bytes(), set.clone())
+                        .with_chunk_size(chunk_size)
+                        .collect::<io::Result<_>>()
+                        .unwrap();
+                    assert_eq!(streamed, parse_located(input, &set));
+                }
+            }
+        }
+
+        // Not valid UTF-8, and a multi-byte character split across chunks
+        let input = b"\xffmul(2,4)\xe2\x82\xacdo()mul(3,";
+        let streamed: Vec<Located> = stream_commands(&input[..], InstructionSet::puzzle())
+            .with_chunk_size(2)
+            .collect::<io::Result<_>>()
+            .unwrap();
+        assert_eq!(
+            streamed,
+            vec![
+                Located {
+                    command: Command::mul(2, 4),
+                    span: 1..9
+                },
+                Located {
+                    command: Command::Do,
+                    span: 12..16
+                }
+            ]
+        )
+    }
+
+    #[test]
+    fn test_stream_commands_long_runs() {
+        let lenient = |leniency| InstructionSet {
+            leniency,
+            ..InstructionSet::extended()
+        };
+        let sets = [
+            InstructionSet::puzzle(),
+            InstructionSet::extended(),
+            lenient(Leniency::Brackets),
+        ];
+
+        // Every candidate is in the same never-ending run of instruction-like bytes
+        let noise = "mul(1,1".repeat(100_000);
+        let start = std::time::Instant::now();
+        for set in &sets {
+            let mut stream = stream_commands(noise.as_bytes(), set.clone()).with_chunk_size(4096);
+            assert!(stream.next().is_none());
+            assert!(stream.buf.len() <= LOOKAHEAD + 4096);
+        }
+        assert!(start.elapsed() < std::time::Duration::from_secs(5));
+
+        // Long lenient instructions are still found across chunks, up to the limit
+        let spaces = |n| " ".repeat(n);
+        let input = format!("mul({}2,{}3)", spaces(1000), spaces(100));
+        let set = lenient(Leniency::Whitespace);
+        let streamed: Vec<Located> = stream_commands(input.as_bytes(), set.clone())
+            .with_chunk_size(7)
+            .collect::<io::Result<_>>()
+            .unwrap();
+        assert_eq!(streamed, parse_located(&input, &set));
+        assert_eq!(streamed.len(), 1);
+
+        let input = format!("mul({}2,3)mul(4,5)", spaces(MAX_STREAMED_INSTRUCTION));
+        let mut stream = stream_commands(input.as_bytes(), set.clone()).with_chunk_size(4096);
+        let found = stream.next().unwrap().unwrap();
+        assert_eq!(found.command, Command::mul(4, 5));
+        assert!(stream.buf.len() <= MAX_STREAMED_INSTRUCTION + 4096);
+        assert_eq!(parse_located(&input, &set).len(), 2)
+    }
+
+    #[test]
+    fn test_lint() {
+        let misses = lint(TEST_INPUT, &InstructionSet::puzzle());
+        let found: Vec<(&str, Leniency)> = misses
+            .iter()
+            .map(|miss| (&TEST_INPUT[miss.span.clone()], miss.needs))
+            .collect();
+        assert_eq!(
+            found,
+            vec![
+                ("mul[3,7]", Leniency::Brackets),
+                ("mul(32,64]", Leniency::Brackets)
+            ]
+        );
+        assert_eq!(misses[0].span.start, 11);
+
+        let input = "mul ( 2 , 4 )mul(1234,5)mul(2,4)mul [1 ,2)";
+        let found: Vec<Leniency> = lint(input, &InstructionSet::puzzle())
+            .into_iter()
+            .map(|miss| miss.needs)
+            .collect();
+        assert_eq!(
+            found,
+            vec![
+                Leniency::Whitespace,
+                Leniency::LongNumbers,
+                Leniency::Brackets
+            ]
+        );
+
+        let both = lint("mul( 1234 ,5)", &InstructionSet::puzzle());
+        assert_eq!(both[0].needs, Leniency::Whitespace);
+        assert_eq!(
+            both[0].relaxations,
+            vec![Relaxation::LongNumber, Relaxation::Whitespace]
+        );
+        assert_eq!(
+            both[0].reason(),
+            "number with more than 3 digits, whitespace inside the call"
+        );
+        let mixed = lint("mul[ 1234 ,5)", &InstructionSet::puzzle());
+        assert_eq!(mixed[0].relaxations.len(), 3);
+
+        let lenient = InstructionSet {
+            leniency: Leniency::Whitespace,
+            ..InstructionSet::puzzle()
+        };
+        let misses = lint(input, &lenient);
+        assert_eq!(misses.len(), 1);
+        assert_eq!(misses[0].relaxations, vec![Relaxation::Bracket]);
+
+        let config: Config = aoc_config::from_str("leniency = \"whitespace\"").unwrap();
+        assert_eq!(config.instruction_set(), lenient);
+        assert!(aoc_config::from_str::<Config>("leniency = \"long_numbers\"").is_err());
+        assert_eq!(
+            process_part_one_with_set::<u64>(input, &lenient),
+            Ok(8 + 6170 + 8)
+        );
+        assert_eq!(process_part_one(input), 8);
+        assert_eq!(process_part_one(TEST_INPUT), 161)
+    }
+
+    #[test]
+    fn test_operand_overflow() {
+        let set = InstructionSet {
+            leniency: Leniency::LongNumbers,
+            ..InstructionSet::puzzle()
+        };
+        let part_one = |input: &str| {
+            (
+                process_part_one_with_set::<u32>(input, &set),
+                process_part_one_with_set::<u64>(input, &set),
+                process_part_one_with_set::<i64>(input, &set).map(|n| n as u128),
+                process_part_one_with_set::<u128>(input, &set),
+            )
+        };
+        const OVERFLOW: Result<u128, EvalError> = Err(EvalError::Overflow);
+
+        let u32_max = u32::MAX as u128;
+        assert_eq!(
+            part_one("mul(4294967295,1)"),
+            (Ok(u32::MAX), Ok(u32::MAX as u64), Ok(u32_max), Ok(u32_max))
+        );
+        assert_eq!(
+            part_one("mul(4294967296,1)"),
+            (
+                Err(EvalError::Overflow),
+                Ok(u32::MAX as u64 + 1),
+                Ok(u32_max + 1),
+                Ok(u32_max + 1)
+            )
+        );
+
+        let i64_max = i64::MAX as u128;
+        assert_eq!(
+            part_one("mul(9223372036854775807,1)"),
+            (
+                Err(EvalError::Overflow),
+                Ok(i64::MAX as u64),
+                Ok(i64_max),
+                Ok(i64_max)
+            )
+        );
+        assert_eq!(
+            part_one("mul(9223372036854775808,1)"),
+            (
+                Err(EvalError::Overflow),
+                Ok(i64::MAX as u64 + 1),
+                OVERFLOW,
+                Ok(i64_max + 1)
+            )
+        );
+
+        let u64_max = u64::MAX as u128;
+        assert_eq!(
+            part_one("mul(18446744073709551615,1)"),
+            (
+                Err(EvalError::Overflow),
+                Ok(u64::MAX),
+                OVERFLOW,
+                Ok(u64_max)
+            )
+        );
+        assert_eq!(
+            part_one("mul(18446744073709551616,1)"),
+            (
+                Err(EvalError::Overflow),
+                Err(EvalError::Overflow),
+                OVERFLOW,
+                Ok(u64_max + 1)
+            )
+        );
+
+        // Past u128::MAX there's no accumulator left to hold it
+        assert_eq!(
+            part_one("mul(340282366920938463463374607431768211455,1)").3,
+            Ok(u128::MAX)
+        );
+        assert_eq!(
+            part_one("mul(340282366920938463463374607431768211456,1)").3,
+            OVERFLOW
+        );
+
+        // The operand used to be skipped, leaving just the second call
+        assert_eq!(
+            process_part_one_with_set::<u128>("mul(4294967296,2)mul(2,3)", &set),
+            Ok(8589934598)
+        );
+        let misses = lint("mul(4294967296,2)mul(2,3)", &InstructionSet::puzzle());
+        assert_eq!(misses.len(), 1);
+        assert_eq!(misses[0].relaxations, vec![Relaxation::LongNumber])
+    }
+
+    #[test]
+    fn test_accumulator_width() {
+        let set = InstructionSet {
+            leniency: Leniency::LongNumbers,
+            ..InstructionSet::extended()
+        };
+        let part_one = |input: &str| {
+            (
+                process_part_one_with_set::<u32>(input, &set),
+                process_part_one_with_set::<u64>(input, &set),
+                process_part_one_with_set::<u128>(input, &set),
+            )
+        };
+
+        assert_eq!(
+            part_one("mul(65535,65537)"),
+            (Ok(4294967295), Ok(4294967295), Ok(4294967295))
+        );
+        assert_eq!(
+            part_one("mul(65536,65536)"),
+            (Err(EvalError::Overflow), Ok(4294967296), Ok(4294967296))
+        );
+        // Each product fits, but not their sum
+        assert_eq!(
+            part_one("mul(65535,65535)mul(65535,65535)"),
+            (Err(EvalError::Overflow), Ok(8589672450), Ok(8589672450))
+        );
+        assert_eq!(
+            part_one("mul(4294967295,4294967295)").1,
+            Ok(18446744065119617025)
+        );
+        assert_eq!(
+            part_one("mul(mul(4294967295,4294967295),2)"),
+            (
+                Err(EvalError::Overflow),
+                Err(EvalError::Overflow),
+                Ok(36893488130239234050)
+            )
+        );
+        assert_eq!(
+            part_one("mul(4294967295,4294967295)mul(4294967295,4294967295)").2,
+            Ok(36893488130239234050)
+        );
+
+        // Unsigned accumulators can't go below zero
+        assert_eq!(part_one("sub(1,2)").1, Err(EvalError::Overflow));
+        assert_eq!(process_part_one_with_set::<i64>("sub(1,2)", &set), Ok(-1));
+
+        // Disabled calls aren't evaluated, so they can't overflow
+        assert_eq!(
+            process_part_two_with_set::<u32>("don't()mul(65536,65536)do()mul(2,3)", &set),
+            Ok(6)
+        );
+
+        let width = |config: &str| aoc_config::from_str::<Config>(config).map(|c| c.width);
+        assert_eq!(width("").unwrap(), Width::U64);
+        assert_eq!(width("width = \"u32\"").unwrap(), Width::U32);
+        assert!(width("width = \"u16\"").is_err());
+        assert_eq!(
+            process_part_one_with_width("mul(65536,65536)", &set, Width::U32),
+            Err(EvalError::Overflow)
+        );
+        assert_eq!(
+            process_part_two_with_width("mul(65536,65536)", &set, Width::U128),
+            Ok("4294967296".to_string())
+        )
+    }
+}
@@ -0,0 +1,47 @@
+use criterion::{criterion_group, criterion_main, BenchmarkId, Criterion};
+
+use day_04::{find_words, find_words_aho_corasick, Boundary, Direction, Grid};
+
+/// Pseudo-random letters from `alphabet`.
+fn letters(seed: &mut u32, alphabet: &[u8], n: usize) -> String {
+    (0..n)
+        .map(|_| {
+            *seed ^= *seed << 13;
+            *seed ^= *seed >> 17;
+            *seed ^= *seed << 5;
+            alphabet[*seed as usize % alphabet.len()] as char
+        })
+        .collect()
+}
+
+fn bench_word_search(c: &mut Criterion) {
+    let mut group = c.benchmark_group("day_04 word search");
+    group.sample_size(10);
+    let alphabet = b"XMASBCDE";
+    let mut seed = 1;
+    let rows: Vec<String> = (0..300)
+        .map(|_| letters(&mut seed, alphabet, 300))
+        .collect();
+    let grid = Grid::parse(&rows.join("\n"), Boundary::Bounded);
+
+    for n_words in [1, 10, 100, 1000] {
+        let words: Vec<String> = (0..n_words)
+            .map(|i| letters(&mut seed, alphabet, 3 + i % 4))
+            .collect();
+        let words: Vec<&str> = words.iter().map(|w| w.as_str()).collect();
+        group.bench_with_input(
+            BenchmarkId::new("recursive", n_words),
+            &words,
+            |b, words| b.iter(|| find_words(&grid, words, &Direction::ALL)),
+        );
+        group.bench_with_input(
+            BenchmarkId::new("aho_corasick", n_words),
+            &words,
+            |b, words| b.iter(|| find_words_aho_corasick(&grid, words, &Direction::ALL)),
+        );
+    }
+    group.finish();
+}
+
+criterion_group!(benches, bench_word_search);
+criterion_main!(benches);
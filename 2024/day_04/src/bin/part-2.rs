@@ -0,0 +1,17 @@
+use aoc_config::CONFIG_FILE;
+use day_04::{process_part_two_with_config, render_part_two, Config};
+use std::{env, fs};
+
+fn main() {
+    let input = fs::read_to_string("input.txt").unwrap();
+    let args: Vec<String> = env::args().collect();
+    let config: Config = aoc_config::load(CONFIG_FILE).unwrap();
+    println!("{}", process_part_two_with_config(&input, &config));
+    if args.iter().any(|arg| arg == "--render") {
+        println!("{}", render_part_two(&input, &config).to_ansi())
+    }
+    if let Some(i) = args.iter().position(|arg| arg == "--html") {
+        let path = args.get(i + 1).expect("missing value for --html");
+        fs::write(path, render_part_two(&input, &config).to_html()).unwrap()
+    }
+}
@@ -0,0 +1,898 @@
+use aho_corasick::AhoCorasick;
+use aoc_config::Validate;
+use serde::Deserialize;
+use std::str::FromStr;
+
+#[derive(Debug, Deserialize)]
+#[serde(default, deny_unknown_fields)]
+pub struct Config {
+    /// Word searched for in part one.
+    pub word: String,
+    /// Shape searched for in part two, in any rotation or reflection. See
+    /// `Template` for the format.
+    pub template: String,
+    /// What's past the edges, for both parts.
+    pub boundary: Boundary,
+}
+
+impl Default for Config {
+    fn default() -> Self {
+        Config {
+            word: "XMAS".to_string(),
+            template: "M.S/.A./M.S".to_string(),
+            boundary: Boundary::Bounded,
+        }
+    }
+}
+
+impl Validate for Config {
+    fn validate(&self) -> Result<(), String> {
+        if self.word.is_empty() {
+            return Err("word must not be empty".to_string());
+        }
+        self.template.parse::<Template>()?;
+        Ok(())
+    }
+}
+
+/// What's past the edges of the grid.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Deserialize)]
+#[serde(rename_all = "lowercase")]
+pub enum Boundary {
+    /// Nothing, including past the end of a short row.
+    Bounded,
+    /// Short rows are padded out to the longest row. Padding only matches
+    /// template wildcards.
+    Padded,
+    /// Padded, and going off one edge comes back on at the opposite edge.
+    Torus,
+}
+
+#[derive(Debug, Clone)]
+pub struct Grid {
+    rows: Vec<Vec<char>>,
+    width: usize,
+    boundary: Boundary,
+}
+
+impl Grid {
+    pub fn parse(input: &str, boundary: Boundary) -> Self {
+        let rows: Vec<Vec<char>> = input.lines().map(|line| line.chars().collect()).collect();
+        let width = rows.iter().map(|row| row.len()).max().unwrap_or(0);
+        Grid {
+            rows,
+            width,
+            boundary,
+        }
+    }
+
+    pub fn rows(&self) -> &[Vec<char>] {
+        &self.rows
+    }
+
+    pub fn height(&self) -> usize {
+        self.rows.len()
+    }
+
+    /// Length of the longest row.
+    pub fn width(&self) -> usize {
+        self.width
+    }
+
+    pub fn boundary(&self) -> Boundary {
+        self.boundary
+    }
+
+    /// Whether `pos` is on the board, even if it's padding.
+    fn contains(&self, pos: (usize, usize)) -> bool {
+        match self.boundary {
+            Boundary::Bounded => pos.0 < self.height() && pos.1 < self.rows[pos.0].len(),
+            Boundary::Padded | Boundary::Torus => pos.0 < self.height() && pos.1 < self.width,
+        }
+    }
+
+    /// The letter at (row, column) `pos`, or `None` for padding or off the board.
+    pub fn get(&self, pos: (usize, usize)) -> Option<char> {
+        self.rows.get(pos.0)?.get(pos.1).copied()
+    }
+
+    /// The cell `offset` (rows, columns) away from `pos`, if it's on the board.
+    fn offset(&self, pos: (usize, usize), offset: (i64, i64)) -> Option<(usize, usize)> {
+        let i = pos.0 as i64 + offset.0;
+        let j = pos.1 as i64 + offset.1;
+        let pos = match self.boundary {
+            Boundary::Torus if self.height() == 0 || self.width == 0 => return None,
+            Boundary::Torus => (
+                i.rem_euclid(self.height() as i64) as usize,
+                j.rem_euclid(self.width as i64) as usize,
+            ),
+            Boundary::Bounded | Boundary::Padded => {
+                (usize::try_from(i).ok()?, usize::try_from(j).ok()?)
+            }
+        };
+        self.contains(pos).then_some(pos)
+    }
+
+    fn step(&self, pos: (usize, usize), dir: Direction) -> Option<(usize, usize)> {
+        let (di, dj) = dir.offset();
+        self.offset(pos, (di as i64, dj as i64))
+    }
+}
+
+fn check_word(
+    grid: &Grid,
+    word: &[char],
+    pos: (usize, usize),
+    dir: Direction,
+    letter_idx: usize,
+) -> bool {
+    if letter_idx == word.len() - 1 {
+        return true;
+    }
+
+    // If matched the next letter then continue recursing
+    match grid.step(pos, dir) {
+        Some(next) if grid.get(next) == Some(word[letter_idx + 1]) => {
+            check_word(grid, word, next, dir, letter_idx + 1)
+        }
+        _ => false,
+    }
+}
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash)]
+pub enum Direction {
+    North,
+    NorthEast,
+    East,
+    SouthEast,
+    South,
+    SouthWest,
+    West,
+    NorthWest,
+}
+
+impl Direction {
+    pub const ALL: [Direction; 8] = [
+        Direction::North,
+        Direction::NorthEast,
+        Direction::East,
+        Direction::SouthEast,
+        Direction::South,
+        Direction::SouthWest,
+        Direction::West,
+        Direction::NorthWest,
+    ];
+
+    /// Directions that don't read backwards: left to right, or straight down.
+    pub const FORWARDS: [Direction; 4] = [
+        Direction::NorthEast,
+        Direction::East,
+        Direction::SouthEast,
+        Direction::South,
+    ];
+
+    /// Change in (row, column) for one step in this direction.
+    pub fn offset(&self) -> (i32, i32) {
+        match self {
+            Direction::North => (-1, 0),
+            Direction::NorthEast => (-1, 1),
+            Direction::East => (0, 1),
+            Direction::SouthEast => (1, 1),
+            Direction::South => (1, 0),
+            Direction::SouthWest => (1, -1),
+            Direction::West => (0, -1),
+            Direction::NorthWest => (-1, -1),
+        }
+    }
+}
+
+/// A word found in the grid, starting at (row, column) `start`.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct Match {
+    pub word: String,
+    pub start: (usize, usize),
+    pub direction: Direction,
+}
+
+impl Match {
+    /// Position of each letter of the word in the grid it was found in.
+    pub fn cells(&self, grid: &Grid) -> Vec<(usize, usize)> {
+        std::iter::successors(Some(self.start), |&pos| grid.step(pos, self.direction))
+            .take(self.word.chars().count())
+            .collect()
+    }
+}
+
+/// Every occurrence of any of `words` reading in one of `directions`, ordered by
+/// start position. Overlapping matches are all included.
+pub fn find_words(grid: &Grid, words: &[&str], directions: &[Direction]) -> Vec<Match> {
+    let words: Vec<Vec<char>> = words
+        .iter()
+        .filter(|word| !word.is_empty())
+        .map(|word| word.chars().collect())
+        .collect();
+    let mut matches = Vec::new();
+
+    for (i, row) in grid.rows().iter().enumerate() {
+        for (j, val) in row.iter().enumerate() {
+            for word in &words {
+                if *val != word[0] {
+                    continue;
+                }
+
+                for dir in directions {
+                    if check_word(grid, word, (i, j), *dir, 0) {
+                        matches.push(Match {
+                            word: word.iter().collect(),
+                            start: (i, j),
+                            direction: *dir,
+                        })
+                    }
+                }
+            }
+        }
+    }
+
+    matches
+}
+
+/// The other way along the same line.
+impl std::ops::Neg for Direction {
+    type Output = Direction;
+
+    fn neg(self) -> Direction {
+        match self {
+            Direction::North => Direction::South,
+            Direction::NorthEast => Direction::SouthWest,
+            Direction::East => Direction::West,
+            Direction::SouthEast => Direction::NorthWest,
+            Direction::South => Direction::North,
+            Direction::SouthWest => Direction::NorthEast,
+            Direction::West => Direction::East,
+            Direction::NorthWest => Direction::SouthEast,
+        }
+    }
+}
+
+/// A straight run of letters through the grid.
+struct Line {
+    text: String,
+    /// Position of the letter each byte of `text` belongs to.
+    cells: Vec<(usize, usize)>,
+    /// For a line that loops back on itself, the length in bytes of one lap.
+    /// `text` carries on past that far enough to find words across the seam.
+    lap: Option<usize>,
+}
+
+impl Line {
+    fn new(cells: impl IntoIterator<Item = (usize, usize)>, grid: &Grid) -> Self {
+        let mut line = Line {
+            text: String::new(),
+            cells: Vec::new(),
+            lap: None,
+        };
+        for pos in cells {
+            let c = grid.get(pos).unwrap();
+            line.text.push(c);
+            line.cells.extend(std::iter::repeat_n(pos, c.len_utf8()));
+        }
+        line
+    }
+}
+
+/// Every maximal straight run of letters through the grid going in `dir`. On a
+/// torus, a loop with no padding in it becomes a single looping line, extended
+/// by `overlap` letters.
+fn lines(grid: &Grid, dir: Direction, overlap: usize) -> Vec<Line> {
+    let is_letter = |pos: Option<(usize, usize)>| pos.and_then(|pos| grid.get(pos)).is_some();
+    let run = |start: (usize, usize)| {
+        std::iter::successors(Some(start), |&pos| {
+            grid.step(pos, dir).filter(|&next| is_letter(Some(next)))
+        })
+    };
+
+    if grid.boundary() != Boundary::Torus {
+        let mut lines = Vec::new();
+        for (i, row) in grid.rows().iter().enumerate() {
+            for j in 0..row.len() {
+                if !is_letter(grid.step((i, j), -dir)) {
+                    lines.push(Line::new(run((i, j)), grid));
+                }
+            }
+        }
+        return lines;
+    }
+
+    let mut lines = Vec::new();
+    let mut seen = vec![vec![false; grid.width()]; grid.height()];
+    for i in 0..grid.height() {
+        for j in 0..grid.width() {
+            if seen[i][j] {
+                continue;
+            }
+            let mut lap = vec![(i, j)];
+            while let Some(next) = grid
+                .step(*lap.last().unwrap(), dir)
+                .filter(|&p| p != (i, j))
+            {
+                lap.push(next);
+            }
+            for &(i, j) in &lap {
+                seen[i][j] = true;
+            }
+
+            match lap.iter().position(|&pos| grid.get(pos).is_none()) {
+                // Start just after some padding, which breaks the loop into runs
+                Some(k) => {
+                    lap.rotate_left(k);
+                    for (k, &pos) in lap.iter().enumerate() {
+                        if is_letter(Some(pos)) && !is_letter(Some(lap[k.saturating_sub(1)])) {
+                            lines.push(Line::new(run(pos), grid));
+                        }
+                    }
+                }
+                None => {
+                    let mut line = Line::new(lap.iter().copied(), grid);
+                    line.lap = Some(line.text.len());
+                    let extra = lap.iter().cycle().take(overlap).copied();
+                    let extra = Line::new(extra, grid);
+                    line.text += &extra.text;
+                    line.cells.extend(extra.cells);
+                    lines.push(line);
+                }
+            }
+        }
+    }
+    lines
+}
+
+/// Same as `find_words`, but reads every row, column and diagonal out of the grid
+/// and runs a single Aho-Corasick automaton over each, which is much faster for
+/// long word lists.
+pub fn find_words_aho_corasick(
+    grid: &Grid,
+    words: &[&str],
+    directions: &[Direction],
+) -> Vec<Match> {
+    let words: Vec<&str> = words.iter().copied().filter(|w| !w.is_empty()).collect();
+    let longest = words.iter().map(|w| w.chars().count()).max().unwrap_or(1);
+    let mut matches = Vec::new();
+
+    // Each line is searched once for the words reading along it and once for the
+    // words reading back the other way
+    for dir in [
+        Direction::East,
+        Direction::South,
+        Direction::SouthEast,
+        Direction::SouthWest,
+    ] {
+        let mut patterns: Vec<(String, usize, Direction)> = Vec::new();
+        for (k, word) in words.iter().enumerate() {
+            if directions.contains(&dir) {
+                patterns.push((word.to_string(), k, dir));
+            }
+            if directions.contains(&-dir) {
+                patterns.push((word.chars().rev().collect(), k, -dir));
+            }
+        }
+        if patterns.is_empty() {
+            continue;
+        }
+        let automaton = AhoCorasick::new(patterns.iter().map(|(p, _, _)| p)).unwrap();
+
+        for Line { text, cells, lap } in lines(grid, dir, longest - 1) {
+            for hit in automaton.find_overlapping_iter(&text) {
+                if lap.is_some_and(|lap| hit.start() >= lap) {
+                    // Already found on the first lap
+                    continue;
+                }
+                let (_, k, direction) = patterns[hit.pattern().as_usize()];
+                let start = if direction == dir {
+                    cells[hit.start()]
+                } else {
+                    cells[hit.end() - 1]
+                };
+                matches.push((
+                    k,
+                    Match {
+                        word: words[k].to_string(),
+                        start,
+                        direction,
+                    },
+                ));
+            }
+        }
+    }
+
+    // Same order as `find_words`
+    let rank = |dir: &Direction| directions.iter().position(|d| d == dir);
+    matches.sort_by_key(|(k, m)| (m.start, *k, rank(&m.direction)));
+    matches.into_iter().map(|(_, m)| m).collect()
+}
+
+pub fn process_part_one(input: &str) -> usize {
+    process_part_one_with_config(input, &Config::default())
+}
+
+pub fn process_part_one_with_config(input: &str, config: &Config) -> usize {
+    let grid = Grid::parse(input, config.boundary);
+    find_words(&grid, &[&config.word], &Direction::ALL).len()
+}
+
+/// A rectangle of letters to look for, where `None` matches anything.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct Template {
+    cells: Vec<Vec<Option<char>>>,
+}
+
+impl FromStr for Template {
+    type Err = String;
+
+    /// Rows are separated by `/` or new lines, and `.` is a wildcard, e.g.
+    /// `M.S/.A./M.S`.
+    fn from_str(s: &str) -> Result<Self, Self::Err> {
+        let cells: Vec<Vec<Option<char>>> = s
+            .split(['/', '\n'])
+            .map(|row| row.trim())
+            .filter(|row| !row.is_empty())
+            .map(|row| row.chars().map(|c| (c != '.').then_some(c)).collect())
+            .collect();
+        if cells.is_empty() {
+            return Err("template must not be empty".to_string());
+        }
+        if cells.iter().any(|row| row.len() != cells[0].len()) {
+            return Err("template rows must all be the same length".to_string());
+        }
+        Ok(Template { cells })
+    }
+}
+
+impl Template {
+    pub fn height(&self) -> usize {
+        self.cells.len()
+    }
+
+    pub fn width(&self) -> usize {
+        self.cells[0].len()
+    }
+
+    /// Turned 90° clockwise.
+    pub fn rotate(&self) -> Template {
+        let cells = (0..self.width())
+            .map(|j| (0..self.height()).rev().map(|i| self.cells[i][j]).collect())
+            .collect();
+        Template { cells }
+    }
+
+    /// Mirrored left to right.
+    pub fn reflect(&self) -> Template {
+        let cells = self
+            .cells
+            .iter()
+            .map(|row| row.iter().rev().copied().collect())
+            .collect();
+        Template { cells }
+    }
+
+    /// Every distinct rotation and reflection, starting with the template itself.
+    pub fn variants(&self) -> Vec<Template> {
+        let mut variants: Vec<Template> = Vec::new();
+        for mut variant in [self.clone(), self.reflect()] {
+            for _ in 0..4 {
+                if !variants.contains(&variant) {
+                    variants.push(variant.clone());
+                }
+                variant = variant.rotate();
+            }
+        }
+        variants
+    }
+
+    /// Wildcards match anything on the board, including padding, but not cells
+    /// off the board.
+    fn matches_at(&self, grid: &Grid, pos: (usize, usize)) -> bool {
+        self.cells.iter().enumerate().all(|(i, row)| {
+            row.iter().enumerate().all(|(j, cell)| {
+                match (grid.offset(pos, (i as i64, j as i64)), cell) {
+                    (Some(pos), Some(c)) => grid.get(pos) == Some(*c),
+                    (Some(_), None) => true,
+                    (None, _) => false,
+                }
+            })
+        })
+    }
+}
+
+/// A template found in the grid, with its top left corner at `start`.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct TemplateMatch {
+    pub start: (usize, usize),
+    /// The rotation or reflection of the template that matched.
+    pub variant: Template,
+}
+
+impl TemplateMatch {
+    /// Position of each letter the template matched, leaving out wildcards.
+    pub fn cells(&self, grid: &Grid) -> Vec<(usize, usize)> {
+        let mut cells = Vec::new();
+        for (i, row) in self.variant.cells.iter().enumerate() {
+            for (j, cell) in row.iter().enumerate() {
+                if cell.is_some() {
+                    cells.extend(grid.offset(self.start, (i as i64, j as i64)));
+                }
+            }
+        }
+        cells
+    }
+}
+
+/// Every place any rotation or reflection of `template` matches the grid, ordered
+/// by position.
+pub fn match_template(grid: &Grid, template: &Template) -> Vec<TemplateMatch> {
+    let variants = template.variants();
+    let mut matches = Vec::new();
+
+    for i in 0..grid.height() {
+        for j in 0..grid.width() {
+            for variant in &variants {
+                if variant.matches_at(grid, (i, j)) {
+                    matches.push(TemplateMatch {
+                        start: (i, j),
+                        variant: variant.clone(),
+                    })
+                }
+            }
+        }
+    }
+
+    matches
+}
+
+pub fn process_part_two(input: &str) -> usize {
+    process_part_two_with_config(input, &Config::default())
+}
+
+pub fn process_part_two_with_config(input: &str, config: &Config) -> usize {
+    let template: Template = config.template.parse().unwrap();
+    match_template(&Grid::parse(input, config.boundary), &template).len()
+}
+
+const COLOURS: [&str; 6] = [
+    "\x1b[1;31m",
+    "\x1b[1;32m",
+    "\x1b[1;33m",
+    "\x1b[1;34m",
+    "\x1b[1;35m",
+    "\x1b[1;36m",
+];
+const RESET: &str = "\x1b[0m";
+const HTML_COLOURS: [&str; 6] = [
+    "#e06c75", "#98c379", "#e5c07b", "#61afef", "#c678dd", "#56b6c2",
+];
+
+/// The grid with only the letters that are part of a match, like the puzzle's
+/// illustrations. Each letter remembers the first match that used it, for
+/// colouring.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct Rendering {
+    cells: Vec<Vec<(char, Option<usize>)>>,
+}
+
+impl Rendering {
+    /// `matches` gives the cells of each match, in order.
+    pub fn new<I>(grid: &Grid, matches: I) -> Self
+    where
+        I: IntoIterator<Item = Vec<(usize, usize)>>,
+    {
+        let mut cells: Vec<Vec<(char, Option<usize>)>> = grid
+            .rows()
+            .iter()
+            .map(|row| row.iter().map(|&c| (c, None)).collect())
+            .collect();
+        for (k, positions) in matches.into_iter().enumerate() {
+            for (i, j) in positions {
+                let owner = &mut cells[i][j].1;
+                owner.get_or_insert(k);
+            }
+        }
+        Rendering { cells }
+    }
+
+    /// Number of letters that are part of at least one match.
+    pub fn used(&self) -> usize {
+        self.cells
+            .iter()
+            .flatten()
+            .filter(|(_, k)| k.is_some())
+            .count()
+    }
+
+    /// Like `Display`, but each match's letters are coloured, cycling through
+    /// six ANSI colours.
+    pub fn to_ansi(&self) -> String {
+        self.render(|c, k| match k {
+            Some(k) => format!("{}{}{}", COLOURS[k % COLOURS.len()], c, RESET),
+            None => ".".to_string(),
+        })
+    }
+
+    /// A standalone HTML page with the same colouring as `to_ansi`.
+    pub fn to_html(&self) -> String {
+        let body = self.render(|c, k| {
+            let c = match c {
+                '&' => "&amp;".to_string(),
+                '<' => "&lt;".to_string(),
+                '>' => "&gt;".to_string(),
+                c => c.to_string(),
+            };
+            match k {
+                Some(k) => format!(
+                    "<span style=\"color: {}\">{}</span>",
+                    HTML_COLOURS[k % HTML_COLOURS.len()],
+                    c
+                ),
+                None => ".".to_string(),
+            }
+        });
+        format!(
+            "<!DOCTYPE html>\n<html>\n<head><meta charset=\"utf-8\"><title>Ceres Search</title></head>\n\
+             <body style=\"background: #282c34; color: #5c6370\">\n<pre>\n{}</pre>\n</body>\n</html>\n",
+            body
+        )
+    }
+
+    fn render(&self, cell: impl Fn(char, Option<usize>) -> String) -> String {
+        let mut out = String::new();
+        for row in &self.cells {
+            for &(c, k) in row {
+                out += &cell(c, k);
+            }
+            out.push('\n');
+        }
+        out
+    }
+}
+
+impl std::fmt::Display for Rendering {
+    fn fmt(&self, f: &mut std::fmt::Formatter) -> std::fmt::Result {
+        let plain = self.render(|c, k| match k {
+            Some(_) => c.to_string(),
+            None => ".".to_string(),
+        });
+        write!(f, "{}", plain.trim_end_matches('\n'))
+    }
+}
+
+/// The grid showing only the letters of the words part one counts.
+pub fn render_part_one(input: &str, config: &Config) -> Rendering {
+    let grid = Grid::parse(input, config.boundary);
+    let matches = find_words(&grid, &[&config.word], &Direction::ALL);
+    Rendering::new(&grid, matches.iter().map(|m| m.cells(&grid)))
+}
+
+/// The grid showing only the letters of the shapes part two counts.
+pub fn render_part_two(input: &str, config: &Config) -> Rendering {
+    let template: Template = config.template.parse().unwrap();
+    let grid = Grid::parse(input, config.boundary);
+    let matches = match_template(&grid, &template);
+    Rendering::new(&grid, matches.iter().map(|m| m.cells(&grid)))
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    const TEST_INPUT: &str = "MMMSXXMASM
+MSAMXMSMSA
+AMXSXMAAMM
+MSAMASMSMX
+XMASAMXAMM
+XXAMMXXAMA
+SMSMSASXSS
+SAXAMASAAA
+MAMMMXMMMM
+MXMXAXMASX";
+
+    #[test]
+    fn test_part_one() {
+        assert_eq!(process_part_one(TEST_INPUT), 18)
+    }
+
+    #[test]
+    fn test_part_one_custom_word() {
+        let config: Config = aoc_config::from_str("word = \"MAS\"").unwrap();
+        assert_eq!(process_part_one_with_config(TEST_INPUT, &config), 38)
+    }
+
+    #[test]
+    fn test_part_two() {
+        assert_eq!(process_part_two(TEST_INPUT), 9)
+    }
+
+    #[test]
+    fn test_find_words() {
+        let grid = Grid::parse(TEST_INPUT, Boundary::Bounded);
+        let matches = find_words(&grid, &["XMAS", "SAMX", "MAS"], &Direction::ALL);
+        let count = |word: &str| matches.iter().filter(|m| m.word == word).count();
+        assert_eq!(count("XMAS"), 18);
+        assert_eq!(count("SAMX"), 18);
+        assert_eq!(count("MAS"), 38);
+        assert_eq!(
+            find_words(&grid, &["XMAS"], &Direction::ALL)[0],
+            Match {
+                word: "XMAS".to_string(),
+                start: (0, 4),
+                direction: Direction::SouthEast
+            }
+        );
+
+        let forwards = find_words(&grid, &["XMAS"], &Direction::FORWARDS);
+        assert_eq!(forwards.len(), 9);
+        assert!(forwards
+            .iter()
+            .all(|m| Direction::FORWARDS.contains(&m.direction)));
+        assert_eq!(find_words(&grid, &[""], &Direction::ALL), vec![])
+    }
+
+    #[test]
+    fn test_template() {
+        let template: Template = "M.S\n.A.\nM.S".parse().unwrap();
+        assert_eq!(template.variants().len(), 4);
+        assert_eq!(template.rotate(), "M.M/.A./S.S".parse().unwrap());
+        assert_eq!(template.reflect(), "S.M/.A./S.M".parse().unwrap());
+        assert!("MS/A".parse::<Template>().is_err());
+
+        let grid = Grid::parse(TEST_INPUT, Boundary::Bounded);
+        let matches = match_template(&grid, &template);
+        assert_eq!(matches.len(), 9);
+        assert_eq!(matches[0].start, (0, 1));
+        assert_eq!(matches[0].variant, "M.S/.A./M.S".parse().unwrap());
+
+        // No symmetry, so all 8 orientations are distinct
+        let template: Template = "XM/.A".parse().unwrap();
+        assert_eq!(template.variants().len(), 8);
+        assert_eq!(
+            match_template(&Grid::parse("AX\nMM", Boundary::Bounded), &template).len(),
+            0
+        );
+        assert_eq!(
+            match_template(&Grid::parse("XA\nMX", Boundary::Bounded), &template).len(),
+            0
+        );
+        assert_eq!(
+            match_template(&Grid::parse("MX\nA.", Boundary::Bounded), &template).len(),
+            1
+        )
+    }
+
+    #[test]
+    fn test_part_two_custom_template() {
+        let config: Config = aoc_config::from_str("template = \"XMAS\"").unwrap();
+        // Rotations only cover horizontal and vertical XMASes, not diagonal ones
+        assert_eq!(process_part_two_with_config(TEST_INPUT, &config), 8);
+        assert!(aoc_config::from_str::<Config>("template = \"XM/A\"").is_err())
+    }
+
+    #[test]
+    fn test_aho_corasick() {
+        let grid = Grid::parse(TEST_INPUT, Boundary::Bounded);
+        let words = ["XMAS", "SAMX", "MAS", "A", "AMMA", "XX"];
+        for directions in [
+            &Direction::ALL[..],
+            &Direction::FORWARDS,
+            &[Direction::West],
+        ] {
+            assert_eq!(
+                find_words_aho_corasick(&grid, &words, directions),
+                find_words(&grid, &words, directions)
+            );
+        }
+
+        let ragged = Grid::parse("ÅBC\nBÅ\nCBÅÅ\nÅ", Boundary::Bounded);
+        let words = ["ÅB", "BÅ", "ÅÅ", "CBÅ"];
+        assert_eq!(
+            find_words_aho_corasick(&ragged, &words, &Direction::ALL),
+            find_words(&ragged, &words, &Direction::ALL)
+        )
+    }
+
+    #[test]
+    fn test_boundaries() {
+        let input = "SAMX\nAX\nXMA";
+        let count = |boundary, word: &str| {
+            let grid = Grid::parse(input, boundary);
+            find_words(&grid, &[word], &Direction::ALL).len()
+        };
+        assert_eq!(count(Boundary::Bounded, "XMAS"), 1);
+        assert_eq!(count(Boundary::Padded, "XMAS"), 1);
+        assert_eq!(count(Boundary::Torus, "XMAS"), 1);
+        // Longer than the row it wraps around
+        assert_eq!(count(Boundary::Bounded, "SAMXSAMX"), 0);
+        assert_eq!(count(Boundary::Torus, "SAMXSAMX"), 1);
+
+        // Wraps around from the end of the first row
+        let input = "ASXM\nBBBB\nSBBB";
+        let count =
+            |boundary| find_words(&Grid::parse(input, boundary), &["XMAS"], &Direction::ALL);
+        assert_eq!(count(Boundary::Bounded).len(), 0);
+        let matches = count(Boundary::Torus);
+        assert_eq!(matches.len(), 1);
+        assert_eq!(
+            (matches[0].start, matches[0].direction),
+            ((0, 2), Direction::East)
+        );
+
+        // Wildcards can cover padding, but not cells off the board
+        let matches = |template: &str, boundary| {
+            let template: Template = template.parse().unwrap();
+            match_template(&Grid::parse("XB\nM", boundary), &template).len()
+        };
+        assert_eq!(matches("X./M.", Boundary::Bounded), 0);
+        assert_eq!(matches("X./M.", Boundary::Padded), 1);
+        assert_eq!(matches("X../M..", Boundary::Padded), 0);
+
+        let config: Config = aoc_config::from_str("boundary = \"torus\"").unwrap();
+        assert_eq!(process_part_one_with_config(TEST_INPUT, &config), 21);
+        assert_eq!(process_part_two_with_config(TEST_INPUT, &config), 10)
+    }
+
+    #[test]
+    fn test_aho_corasick_boundaries() {
+        let words = ["XMAS", "SAMX", "MAS", "A", "AMMA", "XMASXMASXMASX", "ÅÅÅ"];
+        for input in [TEST_INPUT, "SAMX\nAX\nXMA", "ÅXM\nAÅ\nÅ\n\nSÅMX", "XM\nAS"] {
+            for boundary in [Boundary::Bounded, Boundary::Padded, Boundary::Torus] {
+                let grid = Grid::parse(input, boundary);
+                for directions in [&Direction::ALL[..], &Direction::FORWARDS] {
+                    assert_eq!(
+                        find_words_aho_corasick(&grid, &words, directions),
+                        find_words(&grid, &words, directions),
+                        "{:?} {:?}",
+                        input,
+                        boundary
+                    );
+                }
+            }
+        }
+    }
+
+    #[test]
+    fn test_render() {
+        let config = Config::default();
+        let rendering = render_part_one(TEST_INPUT, &config);
+        assert_eq!(
+            rendering.to_string(),
+            "....XXMAS.
+.SAMXMS...
+...S..A...
+..A.A.MS.X
+XMASAMX.MM
+X.....XA.A
+S.S.S.S.SS
+.A.A.A.A.A
+..M.M.M.MM
+.X.X.XMASX"
+        );
+        let rendering = render_part_two(TEST_INPUT, &config);
+        assert_eq!(
+            rendering.to_string(),
+            ".M.S......
+..A..MSMS.
+.M.S.MAA..
+..A.ASMSM.
+.M.S.M....
+..........
+S.S.S.S.S.
+.A.A.A.A..
+M.M.M.M.M.
+.........."
+        );
+        assert_eq!(rendering.used(), 35);
+
+        let rendering = Rendering::new(&Grid::parse("X<\nM", Boundary::Bounded), [vec![(0, 1)]]);
+        assert_eq!(rendering.to_string(), ".<\n.");
+        assert_eq!(rendering.to_ansi(), ".\x1b[1;31m<\x1b[0m\n.\n");
+        assert!(rendering
+            .to_html()
+            .contains("\n.<span style=\"color: #e06c75\">&lt;</span>\n.\n</pre>"));
+    }
+}
@@ -0,0 +1,10 @@
+use day_05::{explain, process_part_two};
+use std::{env, fs};
+
+fn main() {
+    let input = fs::read_to_string("input.txt").unwrap();
+    println!("{}", process_part_two(&input));
+    if env::args().any(|arg| arg == "--explain") {
+        print!("{}", explain(&input))
+    }
+}
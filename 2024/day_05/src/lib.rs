@@ -6,8 +6,11 @@ use nom::{
     IResult,
 };
 use std::collections::hash_map::HashMap;
+use std::fmt;
 
-fn parse_edge(input: &str) -> IResult<&str, (u32, u32)> {
+type Edge = (u32, u32);
+
+fn parse_edge(input: &str) -> IResult<&str, Edge> {
     separated_pair(u32, tag("|"), u32)(input)
 }
 
@@ -15,27 +18,31 @@ fn parse_update(input: &str) -> IResult<&str, Vec<u32>> {
     separated_list1(tag(","), u32)(input)
 }
 
-fn parse_input(input: &str) -> IResult<&str, (Vec<(u32, u32)>, Vec<Vec<u32>>)> {
+fn parse_input(input: &str) -> IResult<&str, (Vec<Edge>, Vec<Vec<u32>>)> {
     let (input, edges) = separated_list1(newline, parse_edge)(input)?;
     let (input, _) = many1(newline)(input)?;
     let (input, updates) = separated_list1(newline, parse_update)(input)?;
     Ok((input, (edges, updates)))
 }
 
+fn is_ordered(update: &[u32], edges: &[Edge]) -> bool {
+    edges.iter().all(|(a, b)| {
+        let Some(pos_a) = update.iter().position(|x| x == a) else {
+            return true;
+        };
+        let Some(pos_b) = update.iter().position(|x| x == b) else {
+            return true;
+        };
+        pos_a < pos_b
+    })
+}
+
 pub fn process_part_one(input: &str) -> u32 {
     let (_, (edges, updates)) = parse_input(input).unwrap();
     updates
         .iter()
         .filter_map(|update| {
-            let valid_order = edges.iter().all(|(a, b)| {
-                let Some(pos_a) = update.iter().position(|x| x == a) else {
-                    return true;
-                };
-                let Some(pos_b) = update.iter().position(|x| x == b) else {
-                    return true;
-                };
-                pos_a < pos_b
-            });
+            let valid_order = is_ordered(update, &edges);
             if valid_order {
                 Some(update[update.len() / 2])
             } else {
@@ -45,7 +52,7 @@ pub fn process_part_one(input: &str) -> u32 {
         .sum()
 }
 
-fn order_update(update: &Vec<u32>, edges: &Vec<(u32, u32)>) -> Vec<u32> {
+fn order_update(update: &[u32], edges: &[Edge]) -> Vec<u32> {
     let mut indegrees: HashMap<u32, usize> = update.iter().map(|v| (*v, 0)).collect();
     let mut edge_map: HashMap<u32, Vec<u32>> = HashMap::new();
     for (a, b) in edges.iter() {
@@ -59,7 +66,7 @@ fn order_update(update: &Vec<u32>, edges: &Vec<(u32, u32)>) -> Vec<u32> {
     }
 
     let mut ordered_update: Vec<u32> = Vec::new();
-    while indegrees.len() > 0 {
+    while !indegrees.is_empty() {
         let sources: Vec<u32> = indegrees
             .iter()
             .filter_map(|(a, b)| if *b == 0 { Some(*a) } else { None })
@@ -83,15 +90,7 @@ pub fn process_part_two(input: &str) -> u32 {
     updates
         .iter()
         .filter_map(|update| {
-            let valid_order = edges.iter().all(|(a, b)| {
-                let Some(pos_a) = update.iter().position(|x| x == a) else {
-                    return true;
-                };
-                let Some(pos_b) = update.iter().position(|x| x == b) else {
-                    return true;
-                };
-                pos_a < pos_b
-            });
+            let valid_order = is_ordered(update, &edges);
             if !valid_order {
                 let ordered_update = order_update(update, &edges);
                 Some(ordered_update[ordered_update.len() / 2])
@@ -102,6 +101,61 @@ pub fn process_part_two(input: &str) -> u32 {
         .sum()
 }
 
+/// Every update with its reordering, if it needed one. Printed by the binaries
+/// with `--explain`.
+#[derive(Debug)]
+pub struct Explanation {
+    pub updates: Vec<(Vec<u32>, Option<Vec<u32>>)>,
+}
+
+fn join(update: &[u32]) -> String {
+    update
+        .iter()
+        .map(|v| v.to_string())
+        .collect::<Vec<String>>()
+        .join(",")
+}
+
+impl fmt::Display for Explanation {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        for (update, reordered) in &self.updates {
+            match reordered {
+                None => writeln!(
+                    f,
+                    "{}: in order, middle page {}",
+                    join(update),
+                    update[update.len() / 2]
+                )?,
+                Some(ordered) => writeln!(
+                    f,
+                    "{}: reordered to {}, middle page {}",
+                    join(update),
+                    join(ordered),
+                    ordered[ordered.len() / 2]
+                )?,
+            }
+        }
+        Ok(())
+    }
+}
+
+pub fn explain(input: &str) -> Explanation {
+    let (_, (edges, updates)) = parse_input(input).unwrap();
+    let updates = updates
+        .into_iter()
+        .map(|update| {
+            let reordered = if is_ordered(&update, &edges) {
+                None
+            } else {
+                Some(order_update(&update, &edges))
+            };
+            (update, reordered)
+        })
+        .collect();
+
+    Explanation { updates }
+}
+
 #[cfg(test)]
 mod tests {
     use super::*;
@@ -144,4 +198,12 @@ mod tests {
     fn test_part_two() {
         assert_eq!(process_part_two(TEST_INPUT), 123)
     }
+
+    #[test]
+    fn test_explain() {
+        let explanation = explain(TEST_INPUT);
+        assert_eq!(explanation.updates[0].1, None);
+        assert_eq!(explanation.updates[3].1, Some(vec![97, 75, 47, 61, 53]));
+        assert_eq!(explanation.updates[4].1, Some(vec![61, 29, 13]))
+    }
 }
@@ -0,0 +1,10 @@
+use day_06::{explain, process_part_one};
+use std::{env, fs};
+
+fn main() {
+    let input = fs::read_to_string("input.txt").unwrap();
+    println!("{}", process_part_one(&input));
+    if env::args().any(|arg| arg == "--explain") {
+        println!("{}", explain(&input))
+    }
+}
@@ -1,9 +1,12 @@
-use day_06::process_part_two;
-use std::fs;
+use day_06::{explain, process_part_two};
+use std::{env, fs};
 
 fn main() {
     let start = std::time::Instant::now();
     let input = fs::read_to_string("input.txt").unwrap();
     println!("{}", process_part_two(&input));
     println!("{:.2?}", start.elapsed());
+    if env::args().any(|arg| arg == "--explain") {
+        println!("{}", explain(&input))
+    }
 }
@@ -1,4 +1,5 @@
 use std::collections::{hash_map::HashMap, hash_set::HashSet};
+use std::fmt;
 
 #[derive(Debug, Clone)]
 struct Grid {
@@ -121,8 +122,7 @@ impl Grid {
     }
 }
 
-pub fn process_part_one(input: &str) -> usize {
-    let grid = Grid::from(input);
+fn find_start(input: &str) -> Point {
     let mut start_pos = Point { row: 0, col: 0 };
     input.lines().enumerate().for_each(|(i, line)| {
         line.chars().enumerate().for_each(|(j, c)| {
@@ -134,7 +134,11 @@ pub fn process_part_one(input: &str) -> usize {
             }
         })
     });
+    start_pos
+}
 
+/// Cells covered by the guard's patrol before leaving the grid.
+fn patrol(grid: &Grid, start_pos: Point) -> HashSet<Point> {
     let mut visited: HashSet<Point> = HashSet::new();
     let mut pos = start_pos;
     let mut dir: Direction = Direction::Up;
@@ -232,7 +236,14 @@ pub fn process_part_one(input: &str) -> usize {
         }
     }
 
-    visited.len()
+    visited
+}
+
+pub fn process_part_one(input: &str) -> usize {
+    let grid = Grid::from(input);
+    let start_pos = find_start(input);
+
+    patrol(&grid, start_pos).len()
 }
 
 fn test_cycle(start_pos: Point, new_obstacle: Point, mut grid: Grid) -> bool {
@@ -274,17 +285,7 @@ fn test_cycle(start_pos: Point, new_obstacle: Point, mut grid: Grid) -> bool {
 
 pub fn process_part_two(input: &str) -> usize {
     let grid = Grid::from(input);
-    let mut start_pos = Point { row: 0, col: 0 };
-    input.lines().enumerate().for_each(|(i, line)| {
-        line.chars().enumerate().for_each(|(j, c)| {
-            if c == '^' {
-                start_pos = Point {
-                    row: i as i32,
-                    col: j as i32,
-                }
-            }
-        })
-    });
+    let start_pos = find_start(input);
 
     let mut new_obstacles: HashSet<Point> = HashSet::new();
     let mut pos = start_pos;
@@ -410,6 +411,44 @@ pub fn process_part_two(input: &str) -> usize {
     new_obstacles.len()
 }
 
+/// The map with every cell the guard visits marked with an `X`, as in the
+/// puzzle. Printed by the binaries with `--explain`.
+#[derive(Debug)]
+pub struct Explanation {
+    pub map: Vec<Vec<char>>,
+    /// Visited cells as `(row, col)`, in reading order.
+    pub visited: Vec<(usize, usize)>,
+}
+
+impl fmt::Display for Explanation {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        let mut map = self.map.clone();
+        for &(row, col) in &self.visited {
+            map[row][col] = 'X';
+        }
+        for row in map {
+            writeln!(f, "{}", row.into_iter().collect::<String>())?;
+        }
+        write!(f, "visited: {}", self.visited.len())
+    }
+}
+
+pub fn explain(input: &str) -> Explanation {
+    let grid = Grid::from(input);
+    let start_pos = find_start(input);
+
+    let mut visited: Vec<(usize, usize)> = patrol(&grid, start_pos)
+        .into_iter()
+        .map(|p| (p.row as usize, p.col as usize))
+        .collect();
+    visited.sort_unstable();
+
+    Explanation {
+        map: input.lines().map(|line| line.chars().collect()).collect(),
+        visited,
+    }
+}
+
 #[cfg(test)]
 mod tests {
     use super::*;
@@ -434,4 +473,13 @@ mod tests {
     fn test_part_two() {
         assert_eq!(process_part_two(TEST_INPUT), 6)
     }
+
+    #[test]
+    fn test_explain() {
+        let explanation = explain(TEST_INPUT);
+        assert_eq!(explanation.visited.len(), 41);
+        assert!(explanation
+            .to_string()
+            .starts_with("....#.....\n....XXXXX#\n"))
+    }
 }
@@ -1,9 +1,12 @@
-use day_07::process_part_one;
-use std::fs;
+use day_07::{explain, process_part_one, PART_ONE_OPS};
+use std::{env, fs};
 
 fn main() {
     let start = std::time::Instant::now();
     let input = fs::read_to_string("input.txt").unwrap();
     println!("{}", process_part_one(&input));
     println!("{:.2?}", start.elapsed());
+    if env::args().any(|arg| arg == "--explain") {
+        print!("{}", explain(&input, &PART_ONE_OPS))
+    }
 }
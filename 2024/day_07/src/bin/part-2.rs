@@ -1,9 +1,12 @@
-use day_13::process_part_two;
-use std::fs;
+use day_07::{explain, process_part_two, PART_TWO_OPS};
+use std::{env, fs};
 
 fn main() {
     let start = std::time::Instant::now();
     let input = fs::read_to_string("input.txt").expect("error loading the input file");
     println!("{}", process_part_two(&input));
     println!("{:.2?}", start.elapsed());
+    if env::args().any(|arg| arg == "--explain") {
+        print!("{}", explain(&input, &PART_TWO_OPS))
+    }
 }
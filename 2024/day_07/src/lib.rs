@@ -0,0 +1,253 @@
+#[cfg(feature = "parallel")]
+use rayon::prelude::*;
+
+use nom::{
+    bytes::complete::tag, character::complete::u64, multi::separated_list1,
+    sequence::separated_pair, IResult,
+};
+use std::fmt;
+
+#[derive(Debug, PartialEq, Eq, Clone, Copy)]
+pub enum Operator {
+    Add,
+    Mul,
+    Combine,
+}
+
+impl Operator {
+    fn inverse_apply(&self, x: &u64, y: &u64) -> Option<u64> {
+        match self {
+            Operator::Add => x.checked_sub(*y),
+            Operator::Mul => x.checked_div(*y),
+            Operator::Combine => Some(x / 10u64.pow(y.ilog10() + 1)),
+        }
+    }
+}
+
+impl fmt::Display for Operator {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match self {
+            Operator::Add => write!(f, "+"),
+            Operator::Mul => write!(f, "*"),
+            Operator::Combine => write!(f, "||"),
+        }
+    }
+}
+
+fn parse_row(input: &str) -> IResult<&str, (u64, Vec<u64>)> {
+    separated_pair(u64, tag(": "), separated_list1(tag(" "), u64))(input)
+}
+
+#[inline]
+fn is_combineable(test_value: u64, num: u64) -> bool {
+    if test_value.checked_sub(num).is_some() {
+        (test_value - num).is_multiple_of(10u64.pow(num.ilog(10) + 1))
+    } else {
+        false
+    }
+}
+
+/// Undoes `op` applied with `num` as its right operand, if that's possible.
+fn undo(op: &Operator, test_value: u64, num: u64) -> Option<u64> {
+    match op {
+        Operator::Mul => {
+            if !test_value.is_multiple_of(num) {
+                return None;
+            }
+        }
+        Operator::Combine => {
+            if !is_combineable(test_value, num) {
+                return None;
+            }
+        }
+        Operator::Add => {}
+    }
+
+    op.inverse_apply(&test_value, &num)
+}
+
+fn check_equation(
+    test_value: u64,
+    idx: usize,
+    nums: &[u64],
+    op: &Operator,
+    available_ops: &[Operator],
+) -> bool {
+    let num = nums[idx];
+
+    let mut valid: bool = false;
+
+    if let Some(val) = undo(op, test_value, num) {
+        if idx == 0 {
+            return val == 0;
+        } else {
+            valid |= available_ops
+                .iter()
+                .any(|op| check_equation(val, idx - 1, nums, op, available_ops));
+        }
+    }
+
+    valid
+}
+
+/// Same search as `check_equation`, but returns the operators placed between
+/// `nums[0..=idx]`, left to right.
+fn find_operators(
+    test_value: u64,
+    idx: usize,
+    nums: &[u64],
+    op: &Operator,
+    available_ops: &[Operator],
+) -> Option<Vec<Operator>> {
+    let val = undo(op, test_value, nums[idx])?;
+    if idx == 0 {
+        return (val == 0).then(Vec::new);
+    }
+
+    let mut ops = available_ops
+        .iter()
+        .find_map(|next| find_operators(val, idx - 1, nums, next, available_ops))?;
+    ops.push(*op);
+    Some(ops)
+}
+
+fn calibration_value(line: &str, available_ops: &[Operator]) -> u64 {
+    let (_, (test_value, nums)) = parse_row(line).unwrap();
+    let valid = available_ops
+        .iter()
+        .any(|op| check_equation(test_value, nums.len() - 1, &nums, op, available_ops));
+
+    match valid {
+        true => test_value,
+        false => 0,
+    }
+}
+
+fn total_calibration(input: &str, available_ops: &[Operator]) -> u64 {
+    #[cfg(feature = "parallel")]
+    let lines = input.par_lines();
+    #[cfg(not(feature = "parallel"))]
+    let lines = input.lines();
+
+    lines
+        .map(|line| calibration_value(line, available_ops))
+        .sum()
+}
+
+pub const PART_ONE_OPS: [Operator; 2] = [Operator::Add, Operator::Mul];
+pub const PART_TWO_OPS: [Operator; 3] = [Operator::Add, Operator::Mul, Operator::Combine];
+
+pub fn process_part_one(input: &str) -> u64 {
+    total_calibration(input, &PART_ONE_OPS)
+}
+
+pub fn process_part_two(input: &str) -> u64 {
+    total_calibration(input, &PART_TWO_OPS)
+}
+
+/// An operator assignment for every equation that has one, using the operators
+/// of the part being explained. Printed by the binaries with `--explain`.
+#[derive(Debug)]
+pub struct Explanation {
+    pub equations: Vec<(u64, Vec<u64>, Option<Vec<Operator>>)>,
+}
+
+impl fmt::Display for Explanation {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        for (test_value, nums, ops) in &self.equations {
+            match ops {
+                Some(ops) => {
+                    write!(f, "{} = {}", test_value, nums[0])?;
+                    for (op, num) in ops.iter().zip(&nums[1..]) {
+                        write!(f, " {} {}", op, num)?;
+                    }
+                    writeln!(f)?
+                }
+                None => {
+                    let nums: Vec<String> = nums.iter().map(|n| n.to_string()).collect();
+                    writeln!(f, "{}: no solution for {}", test_value, nums.join(" "))?
+                }
+            }
+        }
+        Ok(())
+    }
+}
+
+pub fn explain(input: &str, available_ops: &[Operator]) -> Explanation {
+    let equations = input
+        .lines()
+        .map(|line| {
+            let (_, (test_value, nums)) = parse_row(line).unwrap();
+            let ops = available_ops.iter().find_map(|op| {
+                find_operators(test_value, nums.len() - 1, &nums, op, available_ops)
+            });
+            (test_value, nums, ops)
+        })
+        .collect();
+
+    Explanation { equations }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    const TEST_INPUT: &str = "190: 10 19
+3267: 81 40 27
+83: 17 5
+156: 15 6
+7290: 6 8 6 15
+161011: 16 10 13
+192: 17 8 14
+21037: 9 7 18 13
+292: 11 6 16 20";
+
+    #[test]
+    #[allow(clippy::bool_assert_comparison)]
+    fn test_is_combineable() {
+        assert_eq!(is_combineable(12345, 345), true);
+        assert_eq!(is_combineable(1847, 7), true);
+        assert_eq!(is_combineable(1789, 23), false);
+        assert_eq!(is_combineable(76, 6), true);
+        assert_eq!(is_combineable(320, 20), true);
+    }
+
+    #[test]
+    fn test_inverse_apply() {
+        let op = Operator::Combine;
+        assert_eq!(op.inverse_apply(&12345, &345), Some(12));
+        assert_eq!(op.inverse_apply(&5, &5), Some(0));
+        assert_eq!(op.inverse_apply(&96, &6), Some(9));
+    }
+
+    #[test]
+    fn test_part_one() {
+        assert_eq!(process_part_one(TEST_INPUT), 3749)
+    }
+
+    #[test]
+    fn test_part_two() {
+        assert_eq!(process_part_two(TEST_INPUT), 11387)
+    }
+
+    #[test]
+    fn test_explain() {
+        let explanation = explain(TEST_INPUT, &PART_ONE_OPS);
+        assert_eq!(explanation.equations[4].2, None);
+        assert!(explanation
+            .to_string()
+            .contains("7290: no solution for 6 8 6 15\n"));
+
+        let explanation = explain(TEST_INPUT, &PART_TWO_OPS);
+        assert_eq!(
+            explanation.equations[1].2,
+            Some(vec![Operator::Mul, Operator::Add])
+        );
+        assert_eq!(explanation.equations[2].2, None);
+        assert_eq!(
+            explanation.equations[4].2,
+            Some(vec![Operator::Mul, Operator::Combine, Operator::Mul])
+        );
+        assert!(explanation.to_string().contains("7290 = 6 * 8 || 6 * 15\n"))
+    }
+}
@@ -1,9 +1,33 @@
+use aoc_config::Validate;
+use serde::Deserialize;
 use std::collections::HashMap;
 
+#[derive(Debug, Deserialize)]
+#[serde(default, deny_unknown_fields)]
+pub struct Config {
+    pub blinks_part_one: usize,
+    pub blinks_part_two: usize,
+}
+
+impl Default for Config {
+    fn default() -> Self {
+        Config {
+            blinks_part_one: 25,
+            blinks_part_two: 75,
+        }
+    }
+}
+
+impl Validate for Config {
+    fn validate(&self) -> Result<(), String> {
+        Ok(())
+    }
+}
+
 fn apply_rules(num: &usize) -> Vec<usize> {
     if num == &0 {
         vec![1]
-    } else if (num.ilog10() + 1) % 2 == 0 {
+    } else if (num.ilog10() + 1).is_multiple_of(2) {
         let n_digits = num.ilog10() + 1;
         let left = num / 10u64.pow(n_digits / 2) as usize;
         let right = num % (left * 10u64.pow(n_digits / 2) as usize);
@@ -37,23 +61,31 @@ fn run_blinks(stones: Vec<usize>, n: usize) -> usize {
 }
 
 pub fn process_part_one(input: &str) -> usize {
+    process_part_one_with_config(input, &Config::default())
+}
+
+pub fn process_part_one_with_config(input: &str, config: &Config) -> usize {
     let stones: Vec<usize> = input
         .trim()
         .split(" ")
         .filter_map(|n| n.parse::<usize>().ok())
         .collect();
 
-    run_blinks(stones, 25)
+    run_blinks(stones, config.blinks_part_one)
 }
 
 pub fn process_part_two(input: &str) -> usize {
+    process_part_two_with_config(input, &Config::default())
+}
+
+pub fn process_part_two_with_config(input: &str, config: &Config) -> usize {
     let stones: Vec<usize> = input
         .trim()
         .split(" ")
         .filter_map(|n| n.parse::<usize>().ok())
         .collect();
 
-    run_blinks(stones, 75)
+    run_blinks(stones, config.blinks_part_two)
 }
 
 #[cfg(test)]
@@ -67,6 +99,12 @@ mod tests {
         assert_eq!(process_part_one(TEST_INPUT), 55312)
     }
 
+    #[test]
+    fn test_part_one_custom_blinks() {
+        let config: Config = aoc_config::from_str("blinks_part_one = 6").unwrap();
+        assert_eq!(process_part_one_with_config(TEST_INPUT, &config), 22)
+    }
+
     #[test]
     #[ignore]
     fn test_part_two() {
@@ -0,0 +1,11 @@
+use aoc_config::CONFIG_FILE;
+use day_13::{process_part_two_with_config, Config};
+use std::fs;
+
+fn main() {
+    let start = std::time::Instant::now();
+    let input = fs::read_to_string("input.txt").expect("error loading the input file");
+    let config: Config = aoc_config::load(CONFIG_FILE).expect("error loading the config file");
+    println!("{}", process_part_two_with_config(&input, &config));
+    println!("{:.2?}", start.elapsed());
+}
@@ -1,3 +1,4 @@
+use aoc_config::Validate;
 use nom::{
     bytes::complete::tag,
     character::complete::{alpha1, i64, newline},
@@ -5,6 +6,44 @@ use nom::{
     sequence::{preceded, separated_pair, tuple},
     IResult,
 };
+use serde::Deserialize;
+
+/// Largest `prize_offset` accepted. Prizes that far away still leave room in an
+/// `i64` to multiply them by the button offsets.
+pub const MAX_PRIZE_OFFSET: i64 = 1_000_000_000_000_000;
+
+#[derive(Debug, Deserialize)]
+#[serde(default, deny_unknown_fields)]
+pub struct Config {
+    /// Maximum presses of each button in part one.
+    pub max_presses: i64,
+    /// Added to both prize coordinates in part two.
+    pub prize_offset: i64,
+}
+
+impl Default for Config {
+    fn default() -> Self {
+        Config {
+            max_presses: 100,
+            prize_offset: 10000000000000,
+        }
+    }
+}
+
+impl Validate for Config {
+    fn validate(&self) -> Result<(), String> {
+        if self.max_presses < 0 {
+            return Err("max_presses must not be negative".to_string());
+        }
+        if !(0..=MAX_PRIZE_OFFSET).contains(&self.prize_offset) {
+            return Err(format!(
+                "prize_offset must be between 0 and {}",
+                MAX_PRIZE_OFFSET
+            ));
+        }
+        Ok(())
+    }
+}
 
 #[derive(Debug)]
 struct Button {
@@ -49,6 +88,10 @@ fn parse_input(input: &str) -> IResult<&str, Vec<ClawMachine>> {
 }
 
 pub fn process_part_one(input: &str) -> i64 {
+    process_part_one_with_config(input, &Config::default())
+}
+
+pub fn process_part_one_with_config(input: &str, config: &Config) -> i64 {
     let (_, machines) = parse_input(input).unwrap();
     machines
         .iter()
@@ -59,10 +102,8 @@ pub fn process_part_one(input: &str) -> i64 {
                 / (button_a.x * button_b.y - button_a.y * button_b.x);
             let b_presses = (prize_y - a_presses * button_a.y) / button_b.y;
 
-            if a_presses >= 0
-                && a_presses <= 100
-                && b_presses >= 0
-                && b_presses <= 100
+            if (0..=config.max_presses).contains(&a_presses)
+                && (0..=config.max_presses).contains(&b_presses)
                 && (button_a.x * a_presses + button_b.x * b_presses) == prize_x
                 && (button_a.y * a_presses + button_b.y * b_presses) == prize_y
             {
@@ -75,11 +116,18 @@ pub fn process_part_one(input: &str) -> i64 {
 }
 
 pub fn process_part_two(input: &str) -> i64 {
+    process_part_two_with_config(input, &Config::default())
+}
+
+pub fn process_part_two_with_config(input: &str, config: &Config) -> i64 {
     let (_, machines) = parse_input(input).unwrap();
     machines
         .iter()
         .map(|mach| {
-            let (prize_x, prize_y) = (mach.prize.0 + 10000000000000, mach.prize.1 + 10000000000000);
+            let (prize_x, prize_y) = (
+                mach.prize.0 + config.prize_offset,
+                mach.prize.1 + config.prize_offset,
+            );
             let (button_a, button_b) = (&mach.button_a, &mach.button_b);
             let a_presses = (prize_x * button_b.y - prize_y * button_b.x)
                 / (button_a.x * button_b.y - button_a.y * button_b.x);
@@ -123,8 +171,27 @@ Prize: X=18641, Y=10279";
         assert_eq!(process_part_one(TEST_INPUT), 480)
     }
 
+    #[test]
+    fn test_part_one_press_cap() {
+        let config: Config = aoc_config::from_str("max_presses = 85").unwrap();
+        assert_eq!(process_part_one_with_config(TEST_INPUT, &config), 280)
+    }
+
     #[test]
     fn test_part_two() {
         assert_eq!(process_part_two(TEST_INPUT), 875318608908)
     }
+
+    #[test]
+    fn test_prize_offset_bounds() {
+        let config: Config =
+            aoc_config::from_str(&format!("prize_offset = {}", MAX_PRIZE_OFFSET)).unwrap();
+        assert!(process_part_two_with_config(TEST_INPUT, &config) >= 0);
+        assert!(aoc_config::from_str::<Config>(&format!(
+            "prize_offset = {}",
+            MAX_PRIZE_OFFSET + 1
+        ))
+        .is_err());
+        assert!(aoc_config::from_str::<Config>("prize_offset = -1").is_err())
+    }
 }
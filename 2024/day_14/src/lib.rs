@@ -1,5 +1,52 @@
+use aoc_config::Validate;
+use serde::Deserialize;
 use std::collections::{HashMap, HashSet};
 
+#[derive(Debug, Deserialize)]
+#[serde(default, deny_unknown_fields)]
+pub struct Config {
+    /// Seconds simulated before counting quadrants in part one.
+    pub seconds: i32,
+    /// Number of seconds searched for the tree in part two.
+    pub search_limit: i32,
+}
+
+impl Default for Config {
+    fn default() -> Self {
+        Config {
+            seconds: 100,
+            search_limit: 10000,
+        }
+    }
+}
+
+impl Validate for Config {
+    fn validate(&self) -> Result<(), String> {
+        if self.seconds < 0 {
+            return Err("seconds must not be negative".to_string());
+        }
+        if self.search_limit <= 0 {
+            return Err("search_limit must be positive".to_string());
+        }
+        Ok(())
+    }
+}
+
+/// Where a robot is after `seconds`, wrapping around the grid. Worked out in
+/// `i64` so that no velocity and number of seconds can overflow.
+fn position_after(
+    (pos_x, pos_y): (i32, i32),
+    (vel_x, vel_y): (i32, i32),
+    seconds: i32,
+    grid_w: i32,
+    grid_h: i32,
+) -> (i32, i32) {
+    let wrap = |pos: i32, vel: i32, size: i32| {
+        (pos as i64 + vel as i64 * seconds as i64).rem_euclid(size as i64) as i32
+    };
+    (wrap(pos_x, vel_x, grid_w), wrap(pos_y, vel_y, grid_h))
+}
+
 fn draw_grid(positions: Vec<(i32, i32)>, grid_w: i32, grid_h: i32) {
     for i in 0..grid_h {
         let mut row: Vec<&str> = Vec::new();
@@ -15,6 +62,15 @@ fn draw_grid(positions: Vec<(i32, i32)>, grid_w: i32, grid_h: i32) {
 }
 
 pub fn process_part_one(input: &str, grid_w: i32, grid_h: i32) -> usize {
+    process_part_one_with_config(input, grid_w, grid_h, &Config::default())
+}
+
+pub fn process_part_one_with_config(
+    input: &str,
+    grid_w: i32,
+    grid_h: i32,
+    config: &Config,
+) -> usize {
     let mut quadrant_map: HashMap<(i32, i32), usize> =
         HashMap::from([((0, 0), 0), ((0, 1), 0), ((1, 0), 0), ((1, 1), 0)]);
 
@@ -29,9 +85,12 @@ pub fn process_part_one(input: &str, grid_w: i32, grid_h: i32) -> usize {
             .map(|(x, y)| (x.parse().unwrap(), y.parse().unwrap()))
             .unwrap();
 
-        let (final_pos_x, final_pos_y) = (
-            (pos_x + vel_x * 100).rem_euclid(grid_w),
-            (pos_y + vel_y * 100).rem_euclid(grid_h),
+        let (final_pos_x, final_pos_y) = position_after(
+            (pos_x, pos_y),
+            (vel_x, vel_y),
+            config.seconds,
+            grid_w,
+            grid_h,
         );
 
         if !(final_pos_x == grid_w / 2 || final_pos_y == grid_h / 2) {
@@ -47,6 +106,10 @@ pub fn process_part_one(input: &str, grid_w: i32, grid_h: i32) -> usize {
 }
 
 pub fn process_part_two(input: &str, grid_w: i32, grid_h: i32) -> i64 {
+    process_part_two_with_config(input, grid_w, grid_h, &Config::default())
+}
+
+pub fn process_part_two_with_config(input: &str, grid_w: i32, grid_h: i32, config: &Config) -> i64 {
     let robots: Vec<((i32, i32), (i32, i32))> = input
         .lines()
         .map(|line| {
@@ -62,17 +125,10 @@ pub fn process_part_two(input: &str, grid_w: i32, grid_h: i32) -> i64 {
             (pos, vel)
         })
         .collect();
-    'outer: for s in 0..10000 {
+    'outer: for s in 0..config.search_limit {
         let positions: Vec<(i32, i32)> = robots
             .iter()
-            .map(|((pos_x, pos_y), (vel_x, vel_y))| {
-                let (final_pos_x, final_pos_y) = (
-                    (pos_x + vel_x * s).rem_euclid(grid_w),
-                    (pos_y + vel_y * s).rem_euclid(grid_h),
-                );
-
-                (final_pos_x, final_pos_y)
-            })
+            .map(|&(pos, vel)| position_after(pos, vel, s, grid_w, grid_h))
             .collect();
         let mut visited: HashSet<(i32, i32)> = HashSet::new();
         let mut to_visit: Vec<(i32, i32)> = vec![];
@@ -128,6 +184,24 @@ p=9,5 v=-3,-3";
         assert_eq!(process_part_one(TEST_INPUT, 11, 7), 12)
     }
 
+    #[test]
+    fn test_part_one_custom_seconds() {
+        let config: Config = aoc_config::from_str("seconds = 1").unwrap();
+        assert_eq!(process_part_one_with_config(TEST_INPUT, 11, 7, &config), 16)
+    }
+
+    #[test]
+    fn test_part_one_large_seconds() {
+        // Robots are back where they were every 11 * 7 seconds
+        let config: Config = aoc_config::from_str("seconds = 2147483647").unwrap();
+        let period: Config =
+            aoc_config::from_str(&format!("seconds = {}", 2147483647 % 77)).unwrap();
+        assert_eq!(
+            process_part_one_with_config(TEST_INPUT, 11, 7, &config),
+            process_part_one_with_config(TEST_INPUT, 11, 7, &period)
+        )
+    }
+
     #[test]
     fn test_part_two() {
         assert_eq!(process_part_two(TEST_INPUT, 11, 7), 10)
@@ -1,5 +1,29 @@
+use aoc_config::Validate;
+use serde::Deserialize;
 use std::collections::{BinaryHeap, HashMap, HashSet};
 
+#[derive(Debug, Deserialize)]
+#[serde(default, deny_unknown_fields)]
+pub struct Config {
+    /// Score added for each 90 degree rotation.
+    pub turn_cost: i32,
+}
+
+impl Default for Config {
+    fn default() -> Self {
+        Config { turn_cost: 1000 }
+    }
+}
+
+impl Validate for Config {
+    fn validate(&self) -> Result<(), String> {
+        if self.turn_cost <= 0 {
+            return Err("turn_cost must be positive".to_string());
+        }
+        Ok(())
+    }
+}
+
 #[derive(Debug, Eq, PartialEq, Copy, Clone, Hash)]
 enum Direction {
     North,
@@ -11,10 +35,10 @@ enum Direction {
 impl Direction {
     fn move_straight(&self, pos: &(i32, i32)) -> (i32, i32) {
         match self {
-            Direction::North => (pos.0 + -1, pos.1 + 0),
-            Direction::East => (pos.0 + 0, pos.1 + 1),
-            Direction::South => (pos.0 + 1, pos.1 + 0),
-            Direction::West => (pos.0 + 0, pos.1 + -1),
+            Direction::North => (pos.0 - 1, pos.1),
+            Direction::East => (pos.0, pos.1 + 1),
+            Direction::South => (pos.0 + 1, pos.1),
+            Direction::West => (pos.0, pos.1 - 1),
         }
     }
 
@@ -45,7 +69,7 @@ enum Block {
     End,
 }
 
-#[derive(Debug, Eq, Hash)]
+#[derive(Debug, Eq)]
 struct Node {
     pos: (i32, i32),
     f_score: i32,
@@ -76,18 +100,14 @@ fn l1_distance(pos: &(i32, i32), end: &(i32, i32)) -> i32 {
     (pos.0 - end.0).abs() + (pos.1 - end.1).abs()
 }
 
-fn get_start_and_end(
-    grid: &Vec<Vec<Block>>,
-    grid_h: usize,
-    grid_w: usize,
-) -> ((i32, i32), (i32, i32)) {
+fn get_start_and_end(grid: &[Vec<Block>]) -> ((i32, i32), (i32, i32)) {
     let mut start_pos: (i32, i32) = (0, 0);
     let mut end_pos: (i32, i32) = (0, 0);
-    for i in 0..grid_h {
-        for j in 0..grid_w {
-            if grid[i][j] == Block::Start {
+    for (i, row) in grid.iter().enumerate() {
+        for (j, block) in row.iter().enumerate() {
+            if *block == Block::Start {
                 start_pos = (i as i32, j as i32);
-            } else if grid[i][j] == Block::End {
+            } else if *block == Block::End {
                 end_pos = (i as i32, j as i32);
             }
         }
@@ -96,11 +116,11 @@ fn get_start_and_end(
     (start_pos, end_pos)
 }
 
-fn find_best_paths(grid: Vec<Vec<Block>>) -> (HashSet<(i32, i32)>, i32) {
+fn find_best_paths(grid: Vec<Vec<Block>>, turn_cost: i32) -> (HashSet<(i32, i32)>, i32) {
     let grid_h = grid.len();
     let grid_w = grid[0].len();
 
-    let (start_pos, end_pos) = get_start_and_end(&grid, grid_h, grid_w);
+    let (start_pos, end_pos) = get_start_and_end(&grid);
 
     let mut to_visit: BinaryHeap<Node> = BinaryHeap::from([Node {
         pos: start_pos,
@@ -153,7 +173,7 @@ fn find_best_paths(grid: Vec<Vec<Block>>) -> (HashSet<(i32, i32)>, i32) {
             let rdir = node.dir.rotate_right();
             match grid[node.pos.0 as usize][node.pos.1 as usize] {
                 Block::FreeSpace | Block::End | Block::Start => {
-                    let g_score = node.g_score - 1000;
+                    let g_score = node.g_score - turn_cost;
                     if lowest_score > 0 && -g_score > lowest_score {
                         continue;
                     }
@@ -173,7 +193,7 @@ fn find_best_paths(grid: Vec<Vec<Block>>) -> (HashSet<(i32, i32)>, i32) {
             let ldir = node.dir.rotate_left();
             match grid[node.pos.0 as usize][node.pos.1 as usize] {
                 Block::FreeSpace | Block::End | Block::Start => {
-                    let g_score = node.g_score - 1000;
+                    let g_score = node.g_score - turn_cost;
                     if lowest_score > 0 && -g_score > lowest_score {
                         continue;
                     }
@@ -196,6 +216,10 @@ fn find_best_paths(grid: Vec<Vec<Block>>) -> (HashSet<(i32, i32)>, i32) {
 }
 
 pub fn process_part_one(input: &str) -> i32 {
+    process_part_one_with_config(input, &Config::default())
+}
+
+pub fn process_part_one_with_config(input: &str, config: &Config) -> i32 {
     let grid: Vec<Vec<Block>> = input
         .lines()
         .map(|line| {
@@ -211,11 +235,15 @@ pub fn process_part_one(input: &str) -> i32 {
         })
         .collect();
 
-    let best_paths = find_best_paths(grid);
+    let best_paths = find_best_paths(grid, config.turn_cost);
     best_paths.1
 }
 
 pub fn process_part_two(input: &str) -> usize {
+    process_part_two_with_config(input, &Config::default())
+}
+
+pub fn process_part_two_with_config(input: &str, config: &Config) -> usize {
     let grid: Vec<Vec<Block>> = input
         .lines()
         .map(|line| {
@@ -231,7 +259,7 @@ pub fn process_part_two(input: &str) -> usize {
         })
         .collect();
 
-    let best_paths = find_best_paths(grid);
+    let best_paths = find_best_paths(grid, config.turn_cost);
     best_paths.0.len()
 }
 
@@ -260,6 +288,12 @@ mod tests {
         assert_eq!(process_part_one(TEST_INPUT), 7036)
     }
 
+    #[test]
+    fn test_part_one_custom_turn_cost() {
+        let config: Config = aoc_config::from_str("turn_cost = 1").unwrap();
+        assert_eq!(process_part_one_with_config(TEST_INPUT, &config), 38)
+    }
+
     #[test]
     fn test_part_two() {
         assert_eq!(process_part_two(TEST_INPUT), 45)
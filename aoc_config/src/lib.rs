@@ -0,0 +1,130 @@
+use serde::de::DeserializeOwned;
+use std::{fmt, fs, io, path::Path};
+
+pub use toml::Table;
+
+/// Name of the per-day config file, looked up next to `input.txt`.
+pub const CONFIG_FILE: &str = "config.toml";
+
+/// Checks a day's parameters once they have been deserialised.
+pub trait Validate {
+    fn validate(&self) -> Result<(), String>;
+}
+
+#[derive(Debug)]
+pub enum ConfigError {
+    Io(io::Error),
+    Parse(toml::de::Error),
+    Invalid(String),
+}
+
+impl fmt::Display for ConfigError {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match self {
+            ConfigError::Io(e) => write!(f, "could not read config: {}", e),
+            ConfigError::Parse(e) => write!(f, "could not parse config: {}", e),
+            ConfigError::Invalid(msg) => write!(f, "invalid config: {}", msg),
+        }
+    }
+}
+
+impl std::error::Error for ConfigError {}
+
+/// Parses and validates a config from a TOML string. Missing keys fall back to
+/// the day's defaults.
+pub fn from_str<T: DeserializeOwned + Validate>(input: &str) -> Result<T, ConfigError> {
+    let config: T = toml::from_str(input).map_err(ConfigError::Parse)?;
+    config.validate().map_err(ConfigError::Invalid)?;
+    Ok(config)
+}
+
+/// Builds and validates a config from an already parsed table, e.g. parameters
+/// received alongside a request rather than read from disk.
+pub fn from_table<T: DeserializeOwned + Validate>(table: Table) -> Result<T, ConfigError> {
+    let config: T = toml::Value::Table(table)
+        .try_into()
+        .map_err(ConfigError::Parse)?;
+    config.validate().map_err(ConfigError::Invalid)?;
+    Ok(config)
+}
+
+/// Loads a config from `path`, or the day's defaults if the file doesn't exist.
+pub fn load<T: DeserializeOwned + Default + Validate>(
+    path: impl AsRef<Path>,
+) -> Result<T, ConfigError> {
+    match fs::read_to_string(path) {
+        Ok(contents) => from_str(&contents),
+        Err(e) if e.kind() == io::ErrorKind::NotFound => Ok(T::default()),
+        Err(e) => Err(ConfigError::Io(e)),
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use serde::Deserialize;
+
+    #[derive(Debug, Deserialize, PartialEq)]
+    #[serde(default, deny_unknown_fields)]
+    struct TestConfig {
+        steps: usize,
+        word: String,
+    }
+
+    impl Default for TestConfig {
+        fn default() -> Self {
+            TestConfig {
+                steps: 25,
+                word: "XMAS".to_string(),
+            }
+        }
+    }
+
+    impl Validate for TestConfig {
+        fn validate(&self) -> Result<(), String> {
+            if self.word.is_empty() {
+                return Err("word must not be empty".to_string());
+            }
+            Ok(())
+        }
+    }
+
+    #[test]
+    fn test_partial_override() {
+        let config: TestConfig = from_str("steps = 3").unwrap();
+        assert_eq!(
+            config,
+            TestConfig {
+                steps: 3,
+                word: "XMAS".to_string()
+            }
+        )
+    }
+
+    #[test]
+    fn test_rejects_invalid() {
+        assert!(matches!(
+            from_str::<TestConfig>("word = \"\""),
+            Err(ConfigError::Invalid(_))
+        ));
+        assert!(matches!(
+            from_str::<TestConfig>("blinks = 3"),
+            Err(ConfigError::Parse(_))
+        ));
+    }
+
+    #[test]
+    fn test_from_table() {
+        let mut table = Table::new();
+        table.insert("steps".to_string(), toml::Value::Integer(7));
+        let config: TestConfig = from_table(table).unwrap();
+        assert_eq!(config.steps, 7);
+        assert_eq!(config.word, "XMAS")
+    }
+
+    #[test]
+    fn test_missing_file_uses_defaults() {
+        let config: TestConfig = load("does-not-exist.toml").unwrap();
+        assert_eq!(config, TestConfig::default())
+    }
+}
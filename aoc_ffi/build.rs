@@ -0,0 +1,24 @@
+use std::{env, path::PathBuf};
+
+/// Set to also write the generated header over the committed `include/aoc.h`.
+const UPDATE_HEADER: &str = "AOC_FFI_UPDATE_HEADER";
+
+fn main() {
+    println!("cargo:rerun-if-changed=src/lib.rs");
+    println!("cargo:rerun-if-changed=cbindgen.toml");
+    println!("cargo:rerun-if-env-changed={}", UPDATE_HEADER);
+
+    let crate_dir = env::var("CARGO_MANIFEST_DIR").unwrap();
+    let out_dir = PathBuf::from(env::var("OUT_DIR").unwrap());
+    let config = cbindgen::Config::from_file("cbindgen.toml").expect("error reading cbindgen.toml");
+    let bindings = cbindgen::Builder::new()
+        .with_crate(crate_dir)
+        .with_config(config)
+        .generate()
+        .expect("error generating the C header");
+    // Only touch the source tree when asked to, so read-only checkouts still build
+    bindings.write_to_file(out_dir.join("aoc.h"));
+    if env::var_os(UPDATE_HEADER).is_some() {
+        bindings.write_to_file("include/aoc.h");
+    }
+}
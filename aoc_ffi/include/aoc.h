@@ -0,0 +1,93 @@
+#ifndef AOC_H
+#define AOC_H
+
+/* Generated by cbindgen from src/lib.rs, do not edit by hand. */
+
+#include <stdarg.h>
+#include <stdbool.h>
+#include <stddef.h>
+#include <stdint.h>
+#include <stdlib.h>
+
+/**
+ * Returned by every `aoc_solve*` call.
+ */
+typedef enum AocStatus {
+  AOC_STATUS_OK = 0,
+  AOC_STATUS_UNKNOWN_PUZZLE = 1,
+  /**
+   * The input isn't valid UTF-8.
+   */
+  AOC_STATUS_INVALID_INPUT = 2,
+  AOC_STATUS_INVALID_PARAMS = 3,
+  /**
+   * The solver panicked, usually because the input failed to parse.
+   */
+  AOC_STATUS_SOLVER_FAILED = 4,
+  /**
+   * `out_buf` can't hold the result; `*out_len` holds the size needed.
+   */
+  AOC_STATUS_BUFFER_TOO_SMALL = 5,
+  AOC_STATUS_NULL_POINTER = 6,
+} AocStatus;
+
+/**
+ * Solves `day`/`part` of 2024 for the `input_len` bytes of UTF-8 at `input`,
+ * writing the answer into `out_buf` as a NUL-terminated string. On entry
+ * `*out_len` is the capacity of `out_buf`; on return it's the number of bytes
+ * the answer (or error message) needs, including the terminator. Pass a NULL
+ * `out_buf` with `*out_len == 0` to query the size.
+ *
+ * # Safety
+ *
+ * `input` must point to `input_len` readable bytes, `out_len` must be a valid
+ * pointer and `out_buf`, if not NULL, must point to `*out_len` writable bytes.
+ */
+enum AocStatus aoc_solve(uint8_t day,
+                         uint8_t part,
+                         const uint8_t *input,
+                         size_t input_len,
+                         char *out_buf,
+                         size_t *out_len);
+
+/**
+ * Same as `aoc_solve`, with `params` a NUL-terminated TOML string overriding
+ * the day's config, in the same format as its `config.toml`.
+ *
+ * # Safety
+ *
+ * As for `aoc_solve`, and `params` must be a valid NUL-terminated string.
+ */
+enum AocStatus aoc_solve_with_params(uint8_t day,
+                                     uint8_t part,
+                                     const uint8_t *input,
+                                     size_t input_len,
+                                     const char *params,
+                                     char *out_buf,
+                                     size_t *out_len);
+
+/**
+ * Same as `aoc_solve_with_params` for a puzzle from any supported `year`.
+ * `params` may be NULL to use the day's defaults.
+ *
+ * # Safety
+ *
+ * As for `aoc_solve`, and `params` must be NULL or a valid NUL-terminated
+ * string.
+ */
+enum AocStatus aoc_solve_year(uint16_t year,
+                              uint8_t day,
+                              uint8_t part,
+                              const uint8_t *input,
+                              size_t input_len,
+                              const char *params,
+                              char *out_buf,
+                              size_t *out_len);
+
+/**
+ * Static description of an `AocStatus` code. The returned string must not be
+ * freed. Takes a plain integer so that any value is safe to pass in.
+ */
+const char *aoc_status_message(uint32_t status);
+
+#endif  /* AOC_H */
@@ -0,0 +1,280 @@
+//! C ABI over every day's solvers, for use from C/C++ or Python's `ctypes`.
+//! The header in `include/aoc.h` is generated from this file by cbindgen; build
+//! with `AOC_FFI_UPDATE_HEADER=1` set to refresh it after changing the API.
+
+use aoc_config::Table;
+use aoc_solvers::{SolveError, DEFAULT_YEAR};
+use std::{
+    ffi::{c_char, CStr},
+    ptr, slice,
+};
+
+/// Returned by every `aoc_solve*` call.
+#[repr(C)]
+#[derive(Debug, PartialEq, Eq, Clone, Copy)]
+pub enum AocStatus {
+    Ok = 0,
+    UnknownPuzzle = 1,
+    /// The input isn't valid UTF-8.
+    InvalidInput = 2,
+    InvalidParams = 3,
+    /// The solver panicked, usually because the input failed to parse.
+    SolverFailed = 4,
+    /// `out_buf` can't hold the result; `*out_len` holds the size needed.
+    BufferTooSmall = 5,
+    NullPointer = 6,
+}
+
+/// Copies `msg` into `out_buf` as a NUL-terminated string if it fits, and sets
+/// `*out_len` to the number of bytes needed including the terminator.
+unsafe fn write_out(msg: &str, out_buf: *mut c_char, out_len: *mut usize) -> bool {
+    let needed = msg.len() + 1;
+    let capacity = *out_len;
+    *out_len = needed;
+    if out_buf.is_null() || capacity < needed {
+        return false;
+    }
+    ptr::copy_nonoverlapping(msg.as_ptr(), out_buf as *mut u8, msg.len());
+    *out_buf.add(msg.len()) = 0;
+    true
+}
+
+unsafe fn solve(
+    year: u16,
+    day: u8,
+    part: u8,
+    input: &[u8],
+    params: &Table,
+    out_buf: *mut c_char,
+    out_len: *mut usize,
+) -> AocStatus {
+    let Ok(input) = std::str::from_utf8(input) else {
+        return AocStatus::InvalidInput;
+    };
+
+    match aoc_solvers::solve(year, day, part, input, params) {
+        Ok(answer) => {
+            if write_out(&answer, out_buf, out_len) {
+                AocStatus::Ok
+            } else {
+                AocStatus::BufferTooSmall
+            }
+        }
+        Err(e) => {
+            // The message is a best-effort extra, the status is what matters
+            write_out(&e.to_string(), out_buf, out_len);
+            match e {
+                SolveError::UnknownPuzzle { .. } => AocStatus::UnknownPuzzle,
+                SolveError::InvalidParams(_) => AocStatus::InvalidParams,
+                SolveError::Failed(_) => AocStatus::SolverFailed,
+            }
+        }
+    }
+}
+
+/// Solves `day`/`part` of 2024 for the `input_len` bytes of UTF-8 at `input`,
+/// writing the answer into `out_buf` as a NUL-terminated string. On entry
+/// `*out_len` is the capacity of `out_buf`; on return it's the number of bytes
+/// the answer (or error message) needs, including the terminator. Pass a NULL
+/// `out_buf` with `*out_len == 0` to query the size.
+///
+/// # Safety
+///
+/// `input` must point to `input_len` readable bytes, `out_len` must be a valid
+/// pointer and `out_buf`, if not NULL, must point to `*out_len` writable bytes.
+#[no_mangle]
+pub unsafe extern "C" fn aoc_solve(
+    day: u8,
+    part: u8,
+    input: *const u8,
+    input_len: usize,
+    out_buf: *mut c_char,
+    out_len: *mut usize,
+) -> AocStatus {
+    aoc_solve_year(
+        DEFAULT_YEAR,
+        day,
+        part,
+        input,
+        input_len,
+        ptr::null(),
+        out_buf,
+        out_len,
+    )
+}
+
+/// Same as `aoc_solve`, with `params` a NUL-terminated TOML string overriding
+/// the day's config, in the same format as its `config.toml`.
+///
+/// # Safety
+///
+/// As for `aoc_solve`, and `params` must be a valid NUL-terminated string.
+#[no_mangle]
+pub unsafe extern "C" fn aoc_solve_with_params(
+    day: u8,
+    part: u8,
+    input: *const u8,
+    input_len: usize,
+    params: *const c_char,
+    out_buf: *mut c_char,
+    out_len: *mut usize,
+) -> AocStatus {
+    if params.is_null() {
+        return AocStatus::NullPointer;
+    }
+    aoc_solve_year(
+        DEFAULT_YEAR,
+        day,
+        part,
+        input,
+        input_len,
+        params,
+        out_buf,
+        out_len,
+    )
+}
+
+/// Same as `aoc_solve_with_params` for a puzzle from any supported `year`.
+/// `params` may be NULL to use the day's defaults.
+///
+/// # Safety
+///
+/// As for `aoc_solve`, and `params` must be NULL or a valid NUL-terminated
+/// string.
+#[no_mangle]
+#[allow(clippy::too_many_arguments)]
+pub unsafe extern "C" fn aoc_solve_year(
+    year: u16,
+    day: u8,
+    part: u8,
+    input: *const u8,
+    input_len: usize,
+    params: *const c_char,
+    out_buf: *mut c_char,
+    out_len: *mut usize,
+) -> AocStatus {
+    if (input.is_null() && input_len > 0) || out_len.is_null() {
+        return AocStatus::NullPointer;
+    }
+    let params: Table = if params.is_null() {
+        Table::new()
+    } else {
+        match CStr::from_ptr(params).to_str().map(str::parse) {
+            Ok(Ok(params)) => params,
+            Ok(Err(e)) => {
+                write_out(&format!("invalid params: {}", e), out_buf, out_len);
+                return AocStatus::InvalidParams;
+            }
+            Err(_) => return AocStatus::InvalidParams,
+        }
+    };
+    let input = if input_len == 0 {
+        &[]
+    } else {
+        slice::from_raw_parts(input, input_len)
+    };
+    solve(year, day, part, input, &params, out_buf, out_len)
+}
+
+/// Static description of an `AocStatus` code. The returned string must not be
+/// freed. Takes a plain integer so that any value is safe to pass in.
+#[no_mangle]
+pub extern "C" fn aoc_status_message(status: u32) -> *const c_char {
+    let msg: &'static CStr = match status {
+        0 => c"ok",
+        1 => c"no solver for this day and part",
+        2 => c"input is not valid UTF-8",
+        3 => c"invalid params",
+        4 => c"solver failed",
+        5 => c"output buffer too small",
+        6 => c"unexpected NULL pointer",
+        _ => c"unknown status",
+    };
+    msg.as_ptr()
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn call(day: u8, part: u8, input: &str, capacity: usize) -> (AocStatus, usize, String) {
+        let mut buf = vec![0 as c_char; capacity];
+        let mut len = capacity;
+        let status = unsafe {
+            aoc_solve(
+                day,
+                part,
+                input.as_ptr(),
+                input.len(),
+                buf.as_mut_ptr(),
+                &mut len,
+            )
+        };
+        let out = if status == AocStatus::BufferTooSmall {
+            String::new()
+        } else {
+            unsafe { CStr::from_ptr(buf.as_ptr()) }
+                .to_string_lossy()
+                .into_owned()
+        };
+        (status, len, out)
+    }
+
+    #[test]
+    fn test_aoc_solve() {
+        let input = "3   4\n4   3\n2   5\n1   3\n3   9\n3   3";
+        assert_eq!(call(1, 2, input, 16), (AocStatus::Ok, 3, "31".to_string()));
+        assert_eq!(call(1, 2, input, 2).0, AocStatus::BufferTooSmall);
+        assert_eq!(call(1, 2, input, 2).1, 3);
+        assert_eq!(call(30, 1, input, 64).0, AocStatus::UnknownPuzzle)
+    }
+
+    #[test]
+    fn test_aoc_solve_year() {
+        let input = "125 17";
+        let solve_year = |year, params: Option<&CStr>| {
+            let mut buf = vec![0 as c_char; 16];
+            let mut len = buf.len();
+            let status = unsafe {
+                aoc_solve_year(
+                    year,
+                    11,
+                    1,
+                    input.as_ptr(),
+                    input.len(),
+                    params.map_or(ptr::null(), CStr::as_ptr),
+                    buf.as_mut_ptr(),
+                    &mut len,
+                )
+            };
+            let out = unsafe { CStr::from_ptr(buf.as_ptr()) };
+            (status, out.to_string_lossy().into_owned())
+        };
+        assert_eq!(solve_year(2024, None), (AocStatus::Ok, "55312".to_string()));
+        assert_eq!(
+            solve_year(2024, Some(c"blinks_part_one = 6")),
+            (AocStatus::Ok, "22".to_string())
+        );
+        assert_eq!(solve_year(2015, None).0, AocStatus::UnknownPuzzle)
+    }
+
+    #[test]
+    fn test_aoc_status_message() {
+        let message = |status| unsafe { CStr::from_ptr(aoc_status_message(status)) };
+        assert_eq!(message(AocStatus::Ok as u32), c"ok");
+        assert_eq!(
+            message(AocStatus::NullPointer as u32),
+            c"unexpected NULL pointer"
+        );
+        assert_eq!(message(7), c"unknown status");
+        assert_eq!(message(u32::MAX), c"unknown status")
+    }
+
+    #[test]
+    fn test_header_up_to_date() {
+        assert!(
+            include_str!(concat!(env!("OUT_DIR"), "/aoc.h")) == include_str!("../include/aoc.h"),
+            "include/aoc.h is out of date, rebuild with AOC_FFI_UPDATE_HEADER=1"
+        )
+    }
+}
@@ -0,0 +1,43 @@
+use std::{
+    env::{self, consts},
+    path::PathBuf,
+    process::Command,
+};
+
+/// Compiles `tests/harness.c` and runs it against the freshly built shared library.
+#[test]
+fn test_c_harness() {
+    let manifest_dir = PathBuf::from(env!("CARGO_MANIFEST_DIR"));
+    // Integration tests live in target/<profile>/deps, which is also where
+    // `cargo test` leaves the cdylib. Only `cargo build` copies it up a level.
+    let deps_dir = env::current_exe().unwrap().parent().unwrap().to_path_buf();
+    let file_name = format!("{}aoc_ffi{}", consts::DLL_PREFIX, consts::DLL_SUFFIX);
+    let library = [deps_dir.clone(), deps_dir.parent().unwrap().to_path_buf()]
+        .into_iter()
+        .map(|dir| dir.join(&file_name))
+        .find(|library| library.exists())
+        .unwrap_or_else(|| panic!("{} not built in {}", file_name, deps_dir.display()));
+
+    let harness = PathBuf::from(env!("CARGO_TARGET_TMPDIR")).join("aoc_ffi_harness");
+    let cc = env::var("CC").unwrap_or_else(|_| "cc".to_string());
+    let status = Command::new(cc)
+        .arg(manifest_dir.join("tests/harness.c"))
+        .arg("-I")
+        .arg(manifest_dir.join("include"))
+        .arg("-o")
+        .arg(&harness)
+        .arg("-ldl")
+        .status()
+        .expect("error running the C compiler");
+    assert!(status.success(), "harness failed to compile");
+
+    let output = Command::new(&harness)
+        .arg(&library)
+        .output()
+        .expect("error running the harness");
+    assert!(
+        output.status.success(),
+        "harness failed:\n{}",
+        String::from_utf8_lossy(&output.stderr)
+    );
+}
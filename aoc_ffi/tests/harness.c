@@ -0,0 +1,106 @@
+/* Loads libaoc_ffi with dlopen and checks the exported functions, the same way
+ * C/C++ or ctypes tooling would. Usage: harness <path to shared library> */
+#include <dlfcn.h>
+#include <stdio.h>
+#include <string.h>
+
+#include "aoc.h"
+
+typedef AocStatus (*solve_fn)(uint8_t, uint8_t, const uint8_t *, size_t, char *, size_t *);
+typedef AocStatus (*solve_with_params_fn)(uint8_t, uint8_t, const uint8_t *, size_t,
+                                          const char *, char *, size_t *);
+typedef AocStatus (*solve_year_fn)(uint16_t, uint8_t, uint8_t, const uint8_t *, size_t,
+                                   const char *, char *, size_t *);
+typedef const char *(*status_message_fn)(uint32_t);
+
+static int failures = 0;
+
+#define CHECK(cond)                                                      \
+    do {                                                                 \
+        if (!(cond)) {                                                   \
+            fprintf(stderr, "%s:%d: check failed: %s\n", __FILE__,       \
+                    __LINE__, #cond);                                    \
+            failures++;                                                  \
+        }                                                                \
+    } while (0)
+
+static const char *DAY_01 = "3   4\n4   3\n2   5\n1   3\n3   9\n3   3";
+
+int main(int argc, char **argv) {
+    if (argc != 2) {
+        fprintf(stderr, "usage: %s <libaoc_ffi>\n", argv[0]);
+        return 2;
+    }
+
+    void *lib = dlopen(argv[1], RTLD_NOW);
+    if (!lib) {
+        fprintf(stderr, "dlopen failed: %s\n", dlerror());
+        return 2;
+    }
+    solve_fn solve = (solve_fn)dlsym(lib, "aoc_solve");
+    solve_with_params_fn solve_with_params =
+        (solve_with_params_fn)dlsym(lib, "aoc_solve_with_params");
+    solve_year_fn solve_year = (solve_year_fn)dlsym(lib, "aoc_solve_year");
+    status_message_fn status_message = (status_message_fn)dlsym(lib, "aoc_status_message");
+    if (!solve || !solve_with_params || !solve_year || !status_message) {
+        fprintf(stderr, "dlsym failed: %s\n", dlerror());
+        return 2;
+    }
+
+    char buf[64];
+    size_t len = sizeof(buf);
+    const uint8_t *input = (const uint8_t *)DAY_01;
+
+    CHECK(solve(1, 1, input, strlen(DAY_01), buf, &len) == AOC_STATUS_OK);
+    CHECK(strcmp(buf, "11") == 0);
+    CHECK(len == 3);
+
+    /* Size query with no buffer */
+    len = 0;
+    CHECK(solve(1, 2, input, strlen(DAY_01), NULL, &len) == AOC_STATUS_BUFFER_TOO_SMALL);
+    CHECK(len == 3);
+
+    len = sizeof(buf);
+    CHECK(solve(25, 1, input, strlen(DAY_01), buf, &len) == AOC_STATUS_UNKNOWN_PUZZLE);
+
+    len = sizeof(buf);
+    CHECK(solve(1, 1, (const uint8_t *)"x y", 3, buf, &len) == AOC_STATUS_SOLVER_FAILED);
+
+    len = sizeof(buf);
+    CHECK(solve(1, 1, (const uint8_t *)"\xff\xfe", 2, buf, &len) == AOC_STATUS_INVALID_INPUT);
+
+    CHECK(solve(1, 1, input, strlen(DAY_01), buf, NULL) == AOC_STATUS_NULL_POINTER);
+
+    len = sizeof(buf);
+    CHECK(solve_with_params(11, 1, (const uint8_t *)"125 17", 6, "blinks_part_one = 6", buf,
+                            &len) == AOC_STATUS_OK);
+    CHECK(strcmp(buf, "22") == 0);
+
+    len = sizeof(buf);
+    CHECK(solve_with_params(11, 1, (const uint8_t *)"125 17", 6, "blinks = 6", buf, &len) ==
+          AOC_STATUS_INVALID_PARAMS);
+
+    len = sizeof(buf);
+    CHECK(solve_year(2024, 1, 1, input, strlen(DAY_01), NULL, buf, &len) == AOC_STATUS_OK);
+    CHECK(strcmp(buf, "11") == 0);
+
+    len = sizeof(buf);
+    CHECK(solve_year(2024, 11, 1, (const uint8_t *)"125 17", 6, "blinks_part_one = 6", buf,
+                     &len) == AOC_STATUS_OK);
+    CHECK(strcmp(buf, "22") == 0);
+
+    len = sizeof(buf);
+    CHECK(solve_year(2015, 1, 1, input, strlen(DAY_01), NULL, buf, &len) ==
+          AOC_STATUS_UNKNOWN_PUZZLE);
+
+    CHECK(strcmp(status_message(AOC_STATUS_OK), "ok") == 0);
+    CHECK(strcmp(status_message(42), "unknown status") == 0);
+
+    dlclose(lib);
+    if (failures) {
+        fprintf(stderr, "%d check(s) failed\n", failures);
+        return 1;
+    }
+    printf("all checks passed\n");
+    return 0;
+}
@@ -0,0 +1,176 @@
+use aoc_config::{ConfigError, Table};
+use std::{
+    fmt,
+    panic::{self, AssertUnwindSafe},
+};
+
+mod y2024;
+
+/// Years with solvers, each living under its own `<year>/day_XX` directory.
+pub const YEARS: [u16; 1] = [2024];
+
+/// Year used by callers that don't ask for one.
+pub const DEFAULT_YEAR: u16 = 2024;
+
+#[derive(Debug)]
+pub enum SolveError {
+    UnknownPuzzle {
+        year: u16,
+        day: u8,
+        part: u8,
+    },
+    InvalidParams(String),
+    /// The solver panicked, almost always because the input failed to parse.
+    Failed(String),
+}
+
+impl fmt::Display for SolveError {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match self {
+            SolveError::UnknownPuzzle { year, day, part } => {
+                write!(f, "no solver for {} day {} part {}", year, day, part)
+            }
+            SolveError::InvalidParams(msg) => write!(f, "{}", msg),
+            SolveError::Failed(msg) => write!(f, "solver failed: {}", msg),
+        }
+    }
+}
+
+impl std::error::Error for SolveError {}
+
+impl From<ConfigError> for SolveError {
+    fn from(e: ConfigError) -> Self {
+        SolveError::InvalidParams(e.to_string())
+    }
+}
+
+pub(crate) fn no_params(day: u8, params: &Table) -> Result<(), SolveError> {
+    if params.is_empty() {
+        Ok(())
+    } else {
+        Err(SolveError::InvalidParams(format!(
+            "day {} does not take any parameters",
+            day
+        )))
+    }
+}
+
+fn catch<T>(solver: impl FnOnce() -> T) -> Result<T, SolveError> {
+    panic::catch_unwind(AssertUnwindSafe(solver)).map_err(|e| {
+        let msg = if let Some(s) = e.downcast_ref::<&str>() {
+            s.to_string()
+        } else if let Some(s) = e.downcast_ref::<String>() {
+            s.clone()
+        } else {
+            "unknown panic".to_string()
+        };
+        SolveError::Failed(msg)
+    })
+}
+
+pub(crate) fn run<T: ToString>(solver: impl FnOnce() -> T) -> Result<String, SolveError> {
+    catch(solver).map(|answer| answer.to_string())
+}
+
+/// Like `run`, for solvers that report bad input as an error instead of
+/// panicking.
+pub(crate) fn try_run<T: ToString, E: ToString>(
+    solver: impl FnOnce() -> Result<T, E>,
+) -> Result<String, SolveError> {
+    catch(solver)?
+        .map(|answer| answer.to_string())
+        .map_err(|e| SolveError::Failed(e.to_string()))
+}
+
+/// Runs the solver for the given year, day and part, with `params` overriding
+/// the day's config (see `aoc_config`).
+pub fn solve(
+    year: u16,
+    day: u8,
+    part: u8,
+    input: &str,
+    params: &Table,
+) -> Result<String, SolveError> {
+    match year {
+        2024 => y2024::solve(day, part, input, params),
+        _ => Err(SolveError::UnknownPuzzle { year, day, part }),
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    const DAY_01_INPUT: &str = "3   4
+4   3
+2   5
+1   3
+3   9
+3   3";
+
+    #[test]
+    fn test_solve() {
+        assert_eq!(
+            solve(2024, 1, 1, DAY_01_INPUT, &Table::new()).unwrap(),
+            "11"
+        );
+        assert_eq!(
+            solve(2024, 1, 2, DAY_01_INPUT, &Table::new()).unwrap(),
+            "31"
+        )
+    }
+
+    #[test]
+    fn test_solve_with_params() {
+        let params: Table = "blinks_part_one = 6".parse().unwrap();
+        assert_eq!(solve(2024, 11, 1, "125 17", &params).unwrap(), "22");
+        // 5000 * 999 * 999 doesn't fit in a u32
+        let input = "mul(999,999)".repeat(5000);
+        let params: Table = "width = \"u32\"".parse().unwrap();
+        assert!(matches!(
+            solve(2024, 3, 1, &input, &params),
+            Err(SolveError::Failed(_))
+        ));
+        let params: Table = "width = \"u128\"".parse().unwrap();
+        assert_eq!(solve(2024, 3, 1, &input, &params).unwrap(), "4990005000");
+        let params: Table = "leniency = \"whitespace\"".parse().unwrap();
+        assert_eq!(
+            solve(2024, 3, 1, "mul ( 2 , 4 )mul(3,5)", &params).unwrap(),
+            "23"
+        )
+    }
+
+    #[test]
+    fn test_solve_errors() {
+        assert!(matches!(
+            solve(2024, 26, 1, "", &Table::new()),
+            Err(SolveError::UnknownPuzzle {
+                year: 2024,
+                day: 26,
+                part: 1
+            })
+        ));
+        assert!(matches!(
+            solve(2015, 1, 1, "", &Table::new()),
+            Err(SolveError::UnknownPuzzle { year: 2015, .. })
+        ));
+        let params: Table = "blinks = 6".parse().unwrap();
+        assert!(matches!(
+            solve(2024, 1, 1, DAY_01_INPUT, &params),
+            Err(SolveError::InvalidParams(_))
+        ));
+        let params: Table = "leniency = \"lenient\"".parse().unwrap();
+        assert!(matches!(
+            solve(2024, 3, 1, "mul(2,4)", &params),
+            Err(SolveError::InvalidParams(_))
+        ));
+        assert!(matches!(
+            solve(2024, 1, 1, "not a number", &Table::new()),
+            Err(SolveError::Failed(_))
+        ));
+        assert!(matches!(
+            solve(2024, 2, 1, "1 2 3\n4 x 6", &Table::new()),
+            Err(SolveError::Failed(msg)) if msg.starts_with("line 2")
+        ))
+    }
+}
@@ -0,0 +1,100 @@
+use crate::{no_params, run, try_run, SolveError};
+use aoc_config::Table;
+
+/// Grid sizes are the ones used for the real puzzle inputs.
+pub(crate) fn solve(day: u8, part: u8, input: &str, params: &Table) -> Result<String, SolveError> {
+    match (day, part) {
+        (2, 1) => {
+            let policy: day_02::SafetyPolicy = aoc_config::from_table(params.clone())?;
+            try_run(|| day_02::process_part_one_with_policy(input, &policy))
+        }
+        (2, 2) => {
+            let policy: day_02::SafetyPolicy = aoc_config::from_table(params.clone())?;
+            try_run(|| day_02::process_part_two_with_policy(input, &policy))
+        }
+        (3, 1) => {
+            let config: day_03::Config = aoc_config::from_table(params.clone())?;
+            let set = config.instruction_set();
+            try_run(|| day_03::process_part_one_with_width(input, &set, config.width))
+        }
+        (3, 2) => {
+            let config: day_03::Config = aoc_config::from_table(params.clone())?;
+            let set = config.instruction_set();
+            try_run(|| day_03::process_part_two_with_width(input, &set, config.width))
+        }
+        (4, 1) => {
+            let config: day_04::Config = aoc_config::from_table(params.clone())?;
+            run(|| day_04::process_part_one_with_config(input, &config))
+        }
+        (4, 2) => {
+            let config: day_04::Config = aoc_config::from_table(params.clone())?;
+            run(|| day_04::process_part_two_with_config(input, &config))
+        }
+        (11, 1) => {
+            let config: day_11::Config = aoc_config::from_table(params.clone())?;
+            run(|| day_11::process_part_one_with_config(input, &config))
+        }
+        (11, 2) => {
+            let config: day_11::Config = aoc_config::from_table(params.clone())?;
+            run(|| day_11::process_part_two_with_config(input, &config))
+        }
+        (13, 1) => {
+            let config: day_13::Config = aoc_config::from_table(params.clone())?;
+            run(|| day_13::process_part_one_with_config(input, &config))
+        }
+        (13, 2) => {
+            let config: day_13::Config = aoc_config::from_table(params.clone())?;
+            run(|| day_13::process_part_two_with_config(input, &config))
+        }
+        (14, 1) => {
+            let config: day_14::Config = aoc_config::from_table(params.clone())?;
+            run(|| day_14::process_part_one_with_config(input, 101, 103, &config))
+        }
+        (14, 2) => {
+            let config: day_14::Config = aoc_config::from_table(params.clone())?;
+            run(|| day_14::process_part_two_with_config(input, 101, 103, &config))
+        }
+        (16, 1) => {
+            let config: day_16::Config = aoc_config::from_table(params.clone())?;
+            run(|| day_16::process_part_one_with_config(input, &config))
+        }
+        (16, 2) => {
+            let config: day_16::Config = aoc_config::from_table(params.clone())?;
+            run(|| day_16::process_part_two_with_config(input, &config))
+        }
+        _ => {
+            no_params(day, params)?;
+            match (day, part) {
+                (1, 1) => run(|| day_01::process_part_one(input)),
+                (1, 2) => run(|| day_01::process_part_two(input)),
+                (5, 1) => run(|| day_05::process_part_one(input)),
+                (5, 2) => run(|| day_05::process_part_two(input)),
+                (6, 1) => run(|| day_06::process_part_one(input)),
+                (6, 2) => run(|| day_06::process_part_two(input)),
+                (7, 1) => run(|| day_07::process_part_one(input)),
+                (7, 2) => run(|| day_07::process_part_two(input)),
+                (8, 1) => run(|| day_08::process_part_one(input)),
+                (8, 2) => run(|| day_08::process_part_two(input)),
+                (9, 1) => run(|| day_09::process_part_one(input)),
+                (9, 2) => run(|| day_09::process_part_two(input)),
+                (10, 1) => run(|| day_10::process_part_one(input)),
+                (10, 2) => run(|| day_10::process_part_two(input)),
+                (12, 1) => run(|| day_12::process_part_one(input)),
+                (12, 2) => run(|| day_12::process_part_two(input)),
+                (15, 1) => run(|| day_15::process_part_one(input)),
+                (15, 2) => run(|| day_15::process_part_two(input)),
+                (17, 1) => run(|| day_17::process_part_one(input)),
+                (17, 2) => run(|| day_17::process_part_two(input)),
+                (18, 1) => run(|| day_18::process_part_one(input, 71, 1024)),
+                (18, 2) => run(|| day_18::process_part_two(input, 71)),
+                (19, 1) => run(|| day_19::process_part_one(input)),
+                (19, 2) => run(|| day_19::process_part_two(input)),
+                _ => Err(SolveError::UnknownPuzzle {
+                    year: 2024,
+                    day,
+                    part,
+                }),
+            }
+        }
+    }
+}
@@ -0,0 +1,41 @@
+//! wasm-bindgen wrapper over every day's solvers. Build with `wasm-pack build`
+//! or `cargo build --release` (the target defaults to wasm32-unknown-unknown).
+//!
+//! Solvers run single threaded since `aoc_solvers` is used without its
+//! `parallel` feature. wasm32 aborts on panic, so a parse error traps rather than
+//! coming back as an error.
+
+use aoc_config::Table;
+use aoc_solvers::DEFAULT_YEAR;
+use wasm_bindgen::prelude::*;
+
+/// Solves `day`/`part` of 2024 for `input`, returning the answer as a string.
+#[wasm_bindgen]
+pub fn solve(day: u8, part: u8, input: &str) -> Result<String, JsError> {
+    solve_year(DEFAULT_YEAR, day, part, input, None)
+}
+
+/// Same as `solve`, with `params` a TOML string overriding the day's config.
+#[wasm_bindgen(js_name = solveWithParams)]
+pub fn solve_with_params(day: u8, part: u8, input: &str, params: &str) -> Result<String, JsError> {
+    solve_year(DEFAULT_YEAR, day, part, input, Some(params.to_string()))
+}
+
+/// Solves `day`/`part` of any supported `year`, with `params` optionally
+/// overriding the day's config as for `solveWithParams`.
+#[wasm_bindgen(js_name = solveYear)]
+pub fn solve_year(
+    year: u16,
+    day: u8,
+    part: u8,
+    input: &str,
+    params: Option<String>,
+) -> Result<String, JsError> {
+    let params: Table = match params {
+        Some(params) => params
+            .parse()
+            .map_err(|e| JsError::new(&format!("invalid params: {}", e)))?,
+        None => Table::new(),
+    };
+    aoc_solvers::solve(year, day, part, input, &params).map_err(|e| JsError::new(&e.to_string()))
+}
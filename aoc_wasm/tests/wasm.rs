@@ -0,0 +1,59 @@
+#![cfg(target_arch = "wasm32")]
+
+use aoc_wasm::{solve, solve_with_params, solve_year};
+use wasm_bindgen_test::*;
+
+const DAY_01_INPUT: &str = "3   4
+4   3
+2   5
+1   3
+3   9
+3   3";
+
+const DAY_07_INPUT: &str = "190: 10 19
+3267: 81 40 27
+83: 17 5
+156: 15 6
+7290: 6 8 6 15
+161011: 16 10 13
+192: 17 8 14
+21037: 9 7 18 13
+292: 11 6 16 20";
+
+#[wasm_bindgen_test]
+fn test_solve() {
+    assert_eq!(solve(1, 1, DAY_01_INPUT).unwrap(), "11");
+    assert_eq!(solve(1, 2, DAY_01_INPUT).unwrap(), "31")
+}
+
+#[wasm_bindgen_test]
+fn test_solve_sequential_day() {
+    assert_eq!(solve(7, 2, DAY_07_INPUT).unwrap(), "11387")
+}
+
+#[wasm_bindgen_test]
+fn test_solve_with_params() {
+    assert_eq!(
+        solve_with_params(11, 1, "125 17", "blinks_part_one = 6").unwrap(),
+        "22"
+    );
+    assert!(solve_with_params(11, 1, "125 17", "blinks = 6").is_err());
+    assert!(solve(30, 1, DAY_01_INPUT).is_err())
+}
+
+#[wasm_bindgen_test]
+fn test_solve_year() {
+    assert_eq!(solve_year(2024, 1, 1, DAY_01_INPUT, None).unwrap(), "11");
+    assert_eq!(
+        solve_year(
+            2024,
+            11,
+            1,
+            "125 17",
+            Some("blinks_part_one = 6".to_string())
+        )
+        .unwrap(),
+        "22"
+    );
+    assert!(solve_year(2015, 1, 1, DAY_01_INPUT, None).is_err())
+}
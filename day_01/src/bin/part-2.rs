@@ -1,7 +0,0 @@
-use day_01::process_part_two;
-use std::fs;
-
-fn main() {
-    let input = fs::read_to_string("input.txt").unwrap();
-    println!("{}", process_part_two(&input))
-}
@@ -0,0 +1,302 @@
+use aoc_config::Table;
+use aoc_solvers::SolveError;
+use serde::{Deserialize, Serialize};
+use std::{
+    io::Read,
+    sync::{
+        atomic::{AtomicUsize, Ordering},
+        mpsc::{self, RecvTimeoutError},
+        Arc,
+    },
+    time::{Duration, Instant},
+};
+use threadpool::ThreadPool;
+
+#[derive(Debug, Deserialize)]
+#[serde(deny_unknown_fields)]
+pub struct SolveRequest {
+    pub input: String,
+    /// Overrides for the day's config, as accepted in its `config.toml`.
+    #[serde(default)]
+    pub params: Table,
+}
+
+#[derive(Debug, Serialize)]
+pub struct SolveResponse {
+    pub year: u16,
+    pub day: u8,
+    pub part: u8,
+    pub answer: Option<String>,
+    pub elapsed_ms: Option<f64>,
+    pub error: Option<String>,
+}
+
+#[derive(Serialize)]
+struct ErrorResponse {
+    error: String,
+}
+
+/// Extracts the year, day and part from a `/year/{y}/day/{n}/part/{p}` path. The
+/// year segments are optional, in which case the year is `None`.
+pub fn parse_route(url: &str) -> Option<(Option<u16>, u8, u8)> {
+    let path = url.split('?').next()?;
+    let segments: Vec<&str> = path.trim_matches('/').split('/').collect();
+    let (year, segments) = match segments.as_slice() {
+        ["year", year, rest @ ..] => (Some(year.parse().ok()?), rest),
+        rest => (None, rest),
+    };
+    let ["day", day, "part", part] = segments else {
+        return None;
+    };
+    Some((year, day.parse().ok()?, part.parse().ok()?))
+}
+
+pub fn error_body(msg: String) -> String {
+    serde_json::to_string(&ErrorResponse { error: msg }).unwrap()
+}
+
+/// Largest request body the server reads, so that a client can't exhaust its
+/// memory. Puzzle inputs are a few tens of KiB.
+pub const MAX_BODY_BYTES: u64 = 4 * 1024 * 1024;
+
+/// Reads a request body of at most `limit` bytes, or returns the status code and
+/// JSON body to reject it with: 413 if it's longer, 400 if it can't be read.
+pub fn read_body(reader: impl Read, limit: u64) -> Result<String, (u16, String)> {
+    let mut body = String::new();
+    match reader.take(limit + 1).read_to_string(&mut body) {
+        Ok(_) if body.len() as u64 > limit => Err((
+            413,
+            error_body(format!("request body is over {} bytes", limit)),
+        )),
+        Ok(_) => Ok(body),
+        Err(e) => Err((400, error_body(e.to_string()))),
+    }
+}
+
+type SolveFn = fn(u16, u8, u8, &str, &Table) -> Result<String, SolveError>;
+
+/// Runs solvers on a fixed pool of worker threads. A request that takes longer
+/// than `timeout` gets a 504, but its worker stays busy until the solver returns
+/// since there's no way to interrupt it. While every worker is busy, new requests
+/// get a 503 straight away rather than queueing behind them.
+pub struct Solvers {
+    pool: ThreadPool,
+    solve: SolveFn,
+    workers: usize,
+    busy: Arc<AtomicUsize>,
+    timeout: Duration,
+    default_year: u16,
+}
+
+/// Frees up a worker slot when the job holding it finishes, even if it panics.
+struct Slot(Arc<AtomicUsize>);
+
+impl Drop for Slot {
+    fn drop(&mut self) {
+        self.0.fetch_sub(1, Ordering::SeqCst);
+    }
+}
+
+impl Solvers {
+    /// `default_year` is used for requests to `/day/{n}/part/{p}`.
+    pub fn new(workers: usize, timeout: Duration, default_year: u16) -> Self {
+        Solvers {
+            pool: ThreadPool::new(workers),
+            solve: aoc_solvers::solve,
+            workers,
+            busy: Arc::new(AtomicUsize::new(0)),
+            timeout,
+            default_year,
+        }
+    }
+
+    /// Handles a single request, returning the status code and the JSON body.
+    pub fn handle(&self, method: &str, url: &str, body: &str) -> (u16, String) {
+        let Some((year, day, part)) = parse_route(url) else {
+            return (404, error_body(format!("no route for {}", url)));
+        };
+        let year = year.unwrap_or(self.default_year);
+        if method != "POST" {
+            return (405, error_body(format!("{} is not allowed", method)));
+        }
+        let request: SolveRequest = match serde_json::from_str(body) {
+            Ok(request) => request,
+            Err(e) => return (400, error_body(format!("invalid request body: {}", e))),
+        };
+
+        let reserved = self
+            .busy
+            .fetch_update(Ordering::SeqCst, Ordering::SeqCst, |busy| {
+                (busy < self.workers).then_some(busy + 1)
+            });
+        if reserved.is_err() {
+            let msg = format!("all {} workers are busy", self.workers);
+            return (503, error_body(msg));
+        }
+        let slot = Slot(Arc::clone(&self.busy));
+
+        let (started_tx, started_rx) = mpsc::channel();
+        let (tx, rx) = mpsc::channel();
+        let solve = self.solve;
+        self.pool.execute(move || {
+            let _slot = slot;
+            let _ = started_tx.send(());
+            let start = Instant::now();
+            let result = solve(year, day, part, &request.input, &request.params);
+            // The receiver is gone if the request already timed out
+            let _ = tx.send((result, start.elapsed()));
+        });
+
+        let mut response = SolveResponse {
+            year,
+            day,
+            part,
+            answer: None,
+            elapsed_ms: None,
+            error: None,
+        };
+        // Only time the solver itself, not however long the job waited for a thread
+        let _ = started_rx.recv();
+        let status = match rx.recv_timeout(self.timeout) {
+            Ok((result, elapsed)) => {
+                response.elapsed_ms = Some(elapsed.as_secs_f64() * 1000.0);
+                match result {
+                    Ok(answer) => {
+                        response.answer = Some(answer);
+                        200
+                    }
+                    Err(e) => {
+                        let status = match e {
+                            SolveError::UnknownPuzzle { .. } => 404,
+                            SolveError::InvalidParams(_) => 400,
+                            SolveError::Failed(_) => 422,
+                        };
+                        response.error = Some(e.to_string());
+                        status
+                    }
+                }
+            }
+            Err(RecvTimeoutError::Timeout) => {
+                response.error = Some(format!("timed out after {:.2?}", self.timeout));
+                504
+            }
+            Err(RecvTimeoutError::Disconnected) => {
+                response.error = Some("worker exited unexpectedly".to_string());
+                500
+            }
+        };
+
+        (status, serde_json::to_string(&response).unwrap())
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use serde_json::Value;
+    use std::{sync::atomic::AtomicBool, thread};
+
+    fn solvers() -> Solvers {
+        Solvers::new(2, Duration::from_millis(200), 2024)
+    }
+
+    fn handle(solvers: &Solvers, url: &str, body: &str) -> (u16, Value) {
+        let (status, body) = solvers.handle("POST", url, body);
+        (status, serde_json::from_str(&body).unwrap())
+    }
+
+    #[test]
+    fn test_parse_route() {
+        assert_eq!(parse_route("/day/11/part/2"), Some((None, 11, 2)));
+        assert_eq!(parse_route("/day/1/part/1/?verbose"), Some((None, 1, 1)));
+        assert_eq!(
+            parse_route("/year/2024/day/3/part/2"),
+            Some((Some(2024), 3, 2))
+        );
+        assert_eq!(parse_route("/year/day/3/part/2"), None);
+        assert_eq!(parse_route("/day/1"), None);
+        assert_eq!(parse_route("/day/x/part/1"), None);
+        assert_eq!(parse_route("/day/1/part/1/extra"), None)
+    }
+
+    #[test]
+    fn test_solve() {
+        let (status, body) = handle(
+            &solvers(),
+            "/day/11/part/1",
+            r#"{"input": "125 17", "params": {"blinks_part_one": 6}}"#,
+        );
+        assert_eq!(status, 200);
+        assert_eq!(body["year"], 2024);
+        assert_eq!(body["answer"], "22");
+        assert!(body["elapsed_ms"].is_f64());
+        assert!(body["error"].is_null())
+    }
+
+    #[test]
+    fn test_errors() {
+        let solvers = solvers();
+        assert_eq!(
+            handle(&solvers, "/day/30/part/1", r#"{"input": ""}"#).0,
+            404
+        );
+        assert_eq!(
+            handle(&solvers, "/year/2015/day/1/part/1", r#"{"input": ""}"#).0,
+            404
+        );
+        assert_eq!(handle(&solvers, "/day/1/part/1", "3 4").0, 400);
+        assert_eq!(solvers.handle("GET", "/day/1/part/1", "").0, 405);
+
+        let (status, body) = handle(&solvers, "/day/1/part/1", r#"{"input": "x y"}"#);
+        assert_eq!(status, 422);
+        assert!(body["answer"].is_null());
+        assert!(body["error"].as_str().unwrap().starts_with("solver failed"))
+    }
+
+    #[test]
+    fn test_read_body() {
+        assert_eq!(read_body("12345".as_bytes(), 5), Ok("12345".to_string()));
+        let (status, body) = read_body("123456".as_bytes(), 5).unwrap_err();
+        assert_eq!(status, 413);
+        assert_eq!(body, r#"{"error":"request body is over 5 bytes"}"#);
+        assert_eq!(read_body([0xff].as_slice(), 5).unwrap_err().0, 400)
+    }
+
+    /// Set to let `blocking_solve` return.
+    static RELEASE: AtomicBool = AtomicBool::new(false);
+
+    /// Echoes the input back, once `RELEASE` is set.
+    fn blocking_solve(_: u16, _: u8, _: u8, input: &str, _: &Table) -> Result<String, SolveError> {
+        while !RELEASE.load(Ordering::SeqCst) {
+            thread::sleep(Duration::from_millis(1));
+        }
+        Ok(input.to_string())
+    }
+
+    #[test]
+    fn test_timeout() {
+        let solvers = Solvers {
+            solve: blocking_solve,
+            ..Solvers::new(1, Duration::from_millis(50), 2024)
+        };
+        let (status, body) = handle(&solvers, "/day/1/part/1", r#"{"input": "slow"}"#);
+        assert_eq!(status, 504);
+        assert_eq!(body["error"], "timed out after 50.00ms");
+        assert!(body["answer"].is_null());
+
+        // The only worker is still running the blocked solver
+        let (status, body) = handle(&solvers, "/day/1/part/1", r#"{"input": "next"}"#);
+        assert_eq!(status, 503);
+        assert_eq!(body["error"], "all 1 workers are busy");
+
+        RELEASE.store(true, Ordering::SeqCst);
+        let start = Instant::now();
+        while solvers.busy.load(Ordering::SeqCst) > 0 {
+            assert!(start.elapsed() < Duration::from_secs(10));
+            thread::sleep(Duration::from_millis(1));
+        }
+        let (status, body) = handle(&solvers, "/day/1/part/1", r#"{"input": "next"}"#);
+        assert_eq!(status, 200);
+        assert_eq!(body["answer"], "next")
+    }
+}
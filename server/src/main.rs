@@ -0,0 +1,103 @@
+use server::{error_body, read_body, Solvers, MAX_BODY_BYTES};
+use std::{
+    env,
+    sync::Arc,
+    thread,
+    time::{Duration, Instant},
+};
+use threadpool::ThreadPool;
+use tiny_http::{Header, Request, Response, Server};
+
+struct Args {
+    port: u16,
+    year: u16,
+    workers: usize,
+    connections: usize,
+    timeout: Duration,
+}
+
+fn parse_args() -> Args {
+    let workers = thread::available_parallelism().map_or(4, |n| n.get());
+    let mut args = Args {
+        port: 8080,
+        year: aoc_solvers::DEFAULT_YEAR,
+        workers,
+        connections: 4 * workers,
+        timeout: Duration::from_secs(30),
+    };
+    let mut argv = env::args().skip(1);
+    while let Some(flag) = argv.next() {
+        let value = argv
+            .next()
+            .unwrap_or_else(|| panic!("missing value for {}", flag));
+        match flag.as_str() {
+            "--port" => args.port = value.parse().expect("invalid port"),
+            "--year" => args.year = value.parse().expect("invalid year"),
+            "--workers" => args.workers = value.parse().expect("invalid number of workers"),
+            "--connections" => {
+                args.connections = value.parse().expect("invalid number of connections")
+            }
+            "--timeout-secs" => {
+                args.timeout = Duration::from_secs(value.parse().expect("invalid timeout"))
+            }
+            _ => panic!("unrecognised argument {}", flag),
+        }
+    }
+    if !aoc_solvers::YEARS.contains(&args.year) {
+        panic!("no solvers for {}", args.year);
+    }
+    args
+}
+
+fn respond(request: Request, start: Instant, status: u16, json: String) {
+    println!(
+        "{} {} {} {:.2?}",
+        request.method(),
+        request.url(),
+        status,
+        start.elapsed()
+    );
+    let header = Header::from_bytes("Content-Type", "application/json").unwrap();
+    let response = Response::from_string(json)
+        .with_status_code(status)
+        .with_header(header);
+    if let Err(e) = request.respond(response) {
+        eprintln!("error sending response: {}", e);
+    }
+}
+
+fn main() {
+    let args = parse_args();
+    let server = Server::http(("127.0.0.1", args.port)).expect("error starting the server");
+    let solvers = Arc::new(Solvers::new(args.workers, args.timeout, args.year));
+    // Reading requests and waiting on solvers happens here, so that slow clients
+    // can't hold up the solver workers
+    let connections = ThreadPool::new(args.connections);
+    println!(
+        "listening on http://127.0.0.1:{} with {} workers",
+        args.port, args.workers
+    );
+
+    for mut request in server.incoming_requests() {
+        let start = Instant::now();
+        // Every handler is busy and as many requests are waiting for one
+        if connections.queued_count() >= args.connections {
+            let msg = format!("all {} connections are busy", args.connections);
+            respond(request, start, 503, error_body(msg));
+            continue;
+        }
+        if request.body_length().unwrap_or(0) as u64 > MAX_BODY_BYTES {
+            let msg = format!("request body is over {} bytes", MAX_BODY_BYTES);
+            respond(request, start, 413, error_body(msg));
+            continue;
+        }
+        let solvers = Arc::clone(&solvers);
+        connections.execute(move || {
+            let (status, json) = match read_body(request.as_reader(), MAX_BODY_BYTES) {
+                Ok(body) => solvers.handle(request.method().as_str(), request.url(), &body),
+                Err(rejection) => rejection,
+            };
+            respond(request, start, status, json);
+        });
+    }
+}